@@ -0,0 +1,178 @@
+use std::fmt::{self, Debug, Display};
+use crate::random::Rng;
+
+// Most accepted shrinking steps before settling for the counterexample found so far
+const MAX_SHRINKS: usize = 1000;
+
+type Function<'a, I, O> = Box<dyn Fn(&I) -> O + 'a>;
+
+// Two or more implementations of one answer run side by side on generated inputs, e.g.
+//
+//     Differential::new(|rng| Day14.generate(rng, 5))
+//         .shrink_with(|text| shrink_lines(text))
+//         .implementation("whole flights", ...)
+//         .implementation("second by second", ...)
+//         .check(50);
+//
+// The first input they disagree on is shrunk for as long as the shrinker offers smaller inputs
+// that still split them.
+pub struct Differential<'a, I, O> {
+    generate: Box<dyn Fn(&mut Rng) -> I + 'a>,
+    shrink: Function<'a, I, Vec<I>>,
+    implementations: Vec<(String, Function<'a, I, O>)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Disagreement<I, O> {
+    pub seed: u64,
+    pub shrinks: usize,
+    pub input: I,
+    pub outputs: Vec<(String, O)>,
+}
+
+impl<I: Debug, O: Debug> Display for Disagreement<I, O> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Implementations disagree on seed {} (shrunk {} times)", self.seed, self.shrinks)?;
+        write!(f, "  input: {:?}", self.input)?;
+        for (name, output) in &self.outputs {
+            write!(f, "\n  {}: {:?}", name, output)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a, I: Debug, O: Debug + PartialEq> Differential<'a, I, O> {
+    pub fn new<G: Fn(&mut Rng) -> I + 'a>(generate: G) -> Differential<'a, I, O> {
+        Differential { generate: Box::new(generate), shrink: Box::new(|_| Vec::new()), implementations: Vec::new() }
+    }
+
+    // Offers smaller versions of an input, most aggressive first
+    pub fn shrink_with<S: Fn(&I) -> Vec<I> + 'a>(mut self, shrink: S) -> Differential<'a, I, O> {
+        self.shrink = Box::new(shrink);
+        self
+    }
+
+    pub fn implementation<F: Fn(&I) -> O + 'a>(mut self, name: &str, implementation: F) -> Differential<'a, I, O> {
+        self.implementations.push((name.to_string(), Box::new(implementation)));
+        self
+    }
+
+    fn outputs(&self, input: &I) -> Vec<(String, O)> {
+        self.implementations.iter().map(|(name, implementation)| (name.clone(), implementation(input))).collect()
+    }
+
+    fn disagree(outputs: &[(String, O)]) -> bool {
+        outputs.iter().any(|(_, output)| *output != outputs[0].1)
+    }
+
+    // Tries seeds `0..cases` and returns the shrunk counterexample for the first one the
+    // implementations disagree on
+    pub fn run(&self, cases: u64) -> Option<Disagreement<I, O>> {
+        assert!(self.implementations.len() >= 2, "Need at least two implementations to compare");
+
+        let (seed, mut input, mut outputs) = (0..cases).find_map(|seed| {
+            let input = (self.generate)(&mut Rng::new(seed));
+            let outputs = self.outputs(&input);
+            match Self::disagree(&outputs) {
+                true => Some((seed, input, outputs)),
+                false => None,
+            }
+        })?;
+
+        let mut shrinks = 0;
+        'shrinking: while shrinks < MAX_SHRINKS {
+            for candidate in (self.shrink)(&input) {
+                let candidate_outputs = self.outputs(&candidate);
+                if Self::disagree(&candidate_outputs) {
+                    input = candidate;
+                    outputs = candidate_outputs;
+                    shrinks += 1;
+                    continue 'shrinking;
+                }
+            }
+            break;
+        }
+
+        Some(Disagreement { seed, shrinks, input, outputs })
+    }
+
+    pub fn check(&self, cases: u64) {
+        if let Some(disagreement) = self.run(cases) {
+            panic!("{}", disagreement);
+        }
+    }
+}
+
+// Every copy of `items` with a run of them taken out, halves first and single items last
+pub fn shrink_items<T: Clone>(items: &[T]) -> Vec<Vec<T>> {
+    let mut smaller = Vec::new();
+    let mut chunk = items.len() / 2;
+    while chunk > 0 {
+        for start in (0..=items.len() - chunk).step_by(chunk) {
+            smaller.push(items[..start].iter().chain(&items[start + chunk..]).cloned().collect());
+        }
+        chunk /= 2;
+    }
+    if items.len() == 1 {
+        smaller.push(Vec::new());
+    }
+    smaller
+}
+
+pub fn shrink_lines(text: &str) -> Vec<String> {
+    shrink_items(&text.lines().collect::<Vec<&str>>()).into_iter().map(|lines| lines.join("\n")).collect()
+}
+
+pub fn shrink_chars(text: &str) -> Vec<String> {
+    shrink_items(&text.chars().collect::<Vec<char>>()).into_iter().map(String::from_iter).collect()
+}
+
+// Steps towards zero: zero itself, then half way, then one closer
+pub fn shrink_number(n: i64) -> Vec<i64> {
+    let mut smaller = vec![0, n / 2, n - n.signum()];
+    smaller.dedup();
+    smaller.retain(|&m| m != n);
+    smaller
+}
+
+#[test]
+fn agreement() {
+    Differential::new(|rng| (0..rng.below(20)).map(|_| rng.range(-100, 100)).collect::<Vec<i64>>())
+        .shrink_with(|items| shrink_items(items))
+        .implementation("iterator", |items| items.iter().sum::<i64>())
+        .implementation("loop", |items| {
+            let mut total = 0;
+            for item in items {
+                total += item;
+            }
+            total
+        })
+        .check(100);
+}
+
+#[test]
+fn shrunk_counterexample() {
+    let differential = Differential::new(|rng| (0..20).map(|_| rng.range(0, 100)).collect::<Vec<i64>>())
+        .shrink_with(|items| shrink_items(items))
+        .implementation("sum", |items| items.iter().sum::<i64>())
+        .implementation("sum of small", |items| items.iter().filter(|&&n| n < 90).sum::<i64>());
+
+    let disagreement = differential.run(10).unwrap();
+    assert_eq!(0, disagreement.seed);
+    assert_eq!(1, disagreement.input.len());
+    assert!(disagreement.input[0] >= 90);
+    assert_eq!(vec![("sum".to_string(), disagreement.input[0]), ("sum of small".to_string(), 0)], disagreement.outputs);
+    assert!(disagreement.to_string().starts_with("Implementations disagree on seed 0"));
+}
+
+#[test]
+fn shrinkers() {
+    assert_eq!(vec!["b\nc", "a\nc", "a\nb"], shrink_lines("a\nb\nc"));
+    assert_eq!(vec!["cd", "ab", "bcd", "acd", "abd", "abc"], shrink_chars("abcd"));
+    assert_eq!(vec![String::new()], shrink_chars("x"));
+    assert!(shrink_chars("").is_empty());
+    assert_eq!(vec![0, 5, 9], shrink_number(10));
+    assert_eq!(vec![0, -1], shrink_number(-2));
+    assert_eq!(vec![0], shrink_number(1));
+    assert!(shrink_number(0).is_empty());
+}
@@ -0,0 +1,229 @@
+use std::ops::{Index, IndexMut};
+use crate::math::modulo;
+use crate::parse::ParseError;
+
+pub type Point = (i32, i32);
+
+const ORTHOGONAL: [Point; 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+const DIAGONAL: [Point; 4] = [(1, -1), (1, 1), (-1, 1), (-1, -1)];
+
+pub fn manhattan_distance(from: Point, to: Point) -> i32 {
+    (from.0 - to.0).abs() + (from.1 - to.1).abs()
+}
+
+pub fn clamp(index: i32, min: i32, max: i32) -> i32 {
+    std::cmp::max(std::cmp::min(index, max), min)
+}
+
+// The four points sharing an edge with `point`: up, right, down and left
+pub fn neighbours4(point: Point) -> impl Iterator<Item = Point> {
+    ORTHOGONAL.iter().map(move |d| (point.0 + d.0, point.1 + d.1))
+}
+
+// The eight points around `point`, diagonals included
+pub fn neighbours8(point: Point) -> impl Iterator<Item = Point> {
+    neighbours4(point).chain(DIAGONAL.iter().map(move |d| (point.0 + d.0, point.1 + d.1)))
+}
+
+// A rectangle of cells addressed by (x, y) points, with (0, 0) at the top left
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T> where T: Clone {
+        Grid { width, height, cells: vec![fill; width * height] }
+    }
+
+    pub fn from_fn<F: FnMut(Point) -> T>(width: usize, height: usize, mut cell: F) -> Grid<T> {
+        let cells = (0..height).flat_map(|y| (0..width).map(move |x| (x as i32, y as i32))).map(&mut cell).collect();
+        Grid { width, height, cells }
+    }
+
+    // One row per line and one cell per character. Every line has to be as wide as the first.
+    pub fn parse<F>(text: &str, cell: F, expected: &str) -> Result<Grid<T>, ParseError>
+        where F: Fn(char) -> Option<T> {
+        let mut cells = Vec::new();
+        let mut width = None;
+
+        for (i, line) in text.lines().enumerate() {
+            let on_line = |error: ParseError| error.on_line(i + 1);
+            let mut row = 0;
+            for (index, c) in line.char_indices() {
+                let token = &line[index..index + c.len_utf8()];
+                match cell(c) {
+                    _ if width.is_some_and(|width| row == width) => return Err(on_line(ParseError::at(line, token, "the end of the row"))),
+                    Some(value) => cells.push(value),
+                    None => return Err(on_line(ParseError::at(line, token, expected))),
+                }
+                row += 1;
+            }
+            match width {
+                Some(width) if row < width => return Err(on_line(ParseError::at_end(line, expected))),
+                None if row == 0 => return Err(on_line(ParseError::at_end(line, expected))),
+                _ => width = Some(row),
+            }
+        }
+
+        match width {
+            Some(width) => Ok(Grid { width, height: cells.len() / width, cells }),
+            None => Err(ParseError::at_end("", expected)),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): Point) -> bool {
+        x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        match self.contains(point) {
+            true => Some(&self.cells[self.offset(point)]),
+            false => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        match self.contains(point) {
+            true => {
+                let offset = self.offset(point);
+                Some(&mut self.cells[offset])
+            },
+            false => None,
+        }
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x as i32, y as i32)))
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    // The orthogonal neighbours of `point` that fall inside the grid
+    pub fn neighbours(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        neighbours4(point).filter(move |&p| self.contains(p))
+    }
+
+    // As `neighbours`, with the diagonals as well
+    pub fn neighbours8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        neighbours8(point).filter(move |&p| self.contains(p))
+    }
+
+    // Shifts row `y` right by `by` cells, wrapping around. Negative amounts shift left.
+    pub fn rotate_row(&mut self, y: usize, by: i32) {
+        let row = &mut self.cells[y * self.width..(y + 1) * self.width];
+        row.rotate_right(modulo(by, self.width as i32) as usize);
+    }
+
+    // Shifts column `x` down by `by` cells, wrapping around. Negative amounts shift up.
+    pub fn rotate_column(&mut self, x: usize, by: i32) where T: Clone {
+        let offsets = (0..self.height).map(|y| y * self.width + x).collect::<Vec<usize>>();
+        let mut column = offsets.iter().map(|&offset| self.cells[offset].clone()).collect::<Vec<T>>();
+        column.rotate_right(modulo(by, self.height as i32) as usize);
+        for (offset, cell) in offsets.into_iter().zip(column) {
+            self.cells[offset] = cell;
+        }
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, cell: F) -> Grid<U> {
+        Grid { width: self.width, height: self.height, cells: self.cells.iter().map(cell).collect() }
+    }
+
+    // One line per row, without a trailing newline
+    pub fn render<F: Fn(&T) -> char>(&self, cell: F) -> String {
+        self.rows().map(|row| row.iter().map(&cell).collect::<String>()).collect::<Vec<String>>().join("\n")
+    }
+
+    fn offset(&self, (x, y): Point) -> usize {
+        y as usize * self.width + x as usize
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        self.get(point).unwrap_or_else(|| panic!("{:?} is outside a {}x{} grid", point, self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(point).unwrap_or_else(|| panic!("{:?} is outside a {}x{} grid", point, width, height))
+    }
+}
+
+#[test]
+fn manhattan() {
+    assert_eq!(12, manhattan_distance((0,0), (10,2)));
+    assert_eq!(5, manhattan_distance((2,3), (0,0)));
+    assert_eq!(4, manhattan_distance((-2,-1), (-1,2)));
+}
+
+#[test]
+fn clamping() {
+    assert_eq!(0, clamp(-1, 0, 2));
+    assert_eq!(2, clamp(3, 0, 2));
+    assert_eq!(1, clamp(1, 0, 2));
+}
+
+#[cfg(test)]
+fn digits(c: char) -> Option<u32> {
+    c.to_digit(10)
+}
+
+#[test]
+fn parsing_and_rendering() {
+    let grid = Grid::parse("123\n456", digits, "a digit").unwrap();
+    assert_eq!((3, 2), (grid.width(), grid.height()));
+    assert_eq!(6, grid[(2, 1)]);
+    assert_eq!(None, grid.get((3, 0)));
+    assert_eq!(None, grid.get((0, -1)));
+    assert_eq!("123\n456", grid.render(|d| char::from_digit(*d, 10).unwrap()));
+    assert_eq!(grid, Grid::from_fn(3, 2, |(x, y)| (y * 3 + x + 1) as u32));
+
+    assert_eq!(Err(ParseError::new(2, 2, "x", "a digit")), Grid::parse("123\n4x6", digits, "a digit"));
+    assert_eq!(Err(ParseError::new(2, 3, "", "a digit")), Grid::parse("123\n45", digits, "a digit"));
+    assert_eq!(Err(ParseError::new(2, 4, "7", "the end of the row")), Grid::parse("123\n4567", digits, "a digit"));
+    assert_eq!(Err(ParseError::new(1, 1, "", "a digit")), Grid::parse("", digits, "a digit"));
+}
+
+#[test]
+fn rotations() {
+    let mut grid = Grid::parse("123\n456\n789", digits, "a digit").unwrap();
+    grid.rotate_row(0, 1);
+    assert_eq!("312\n456\n789", grid.render(|d| char::from_digit(*d, 10).unwrap()));
+    grid.rotate_column(2, -1);
+    assert_eq!("316\n459\n782", grid.render(|d| char::from_digit(*d, 10).unwrap()));
+    grid.rotate_column(2, 4);
+    grid.rotate_row(0, -4);
+    assert_eq!("123\n456\n789", grid.render(|d| char::from_digit(*d, 10).unwrap()));
+}
+
+#[test]
+fn neighbours() {
+    let grid = Grid::new(3, 2, '.');
+    assert_eq!(vec![(1, 0), (0, 1)], grid.neighbours((0, 0)).collect::<Vec<Point>>());
+    assert_eq!(vec![(1, 0), (0, 1), (1, 1)], grid.neighbours8((0, 0)).collect::<Vec<Point>>());
+    assert_eq!(5, grid.neighbours8((1, 1)).count());
+    assert_eq!(8, neighbours8((0, 0)).count());
+    assert_eq!(vec![(5, 4), (6, 5), (5, 6), (4, 5)], neighbours4((5, 5)).collect::<Vec<Point>>());
+}
@@ -0,0 +1,159 @@
+use std::fmt;
+use crate::parse::ParseError;
+
+// A small INI dialect: `[section]` headers, `key = value` pairs, `#` or `;` comments.
+// Values may contain `\n` escapes so multi-line answers fit on one line.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Ini {
+    sections: Vec<(String, Vec<(String, String)>)>,
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn unescape(value: &str) -> String {
+    let mut unescaped = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') => unescaped.push('\n'),
+            Some(other) => unescaped.push(other),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+pub enum IniLine<'a> {
+    Blank,
+    Section(&'a str),
+    Entry(&'a str, &'a str),
+}
+
+// Splits one line of INI text; keys and values are slices of `line` so callers can point errors at them
+pub fn parse_line(line: &str) -> Result<IniLine<'_>, ParseError> {
+    let trimmed = line.trim();
+
+    if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with(';') {
+        return Ok(IniLine::Blank);
+    }
+
+    if let Some(header) = trimmed.strip_prefix('[') {
+        return match header.strip_suffix(']') {
+            Some(name) => Ok(IniLine::Section(name.trim())),
+            None => Err(ParseError::at(line, &trimmed[trimmed.len()..], "a closing ]")),
+        };
+    }
+
+    match trimmed.split_once('=') {
+        Some((key, value)) => Ok(IniLine::Entry(key.trim(), value.trim())),
+        None => Err(ParseError::at(line, trimmed, "key = value")),
+    }
+}
+
+impl Ini {
+    pub fn new() -> Ini {
+        Ini::default()
+    }
+
+    pub fn parse(text: &str) -> Result<Ini, ParseError> {
+        let mut ini = Ini::new();
+
+        for (line_number, line) in text.lines().enumerate() {
+            let on_line = |error: ParseError| error.on_line(line_number + 1);
+
+            match parse_line(line).map_err(on_line)? {
+                IniLine::Blank => {},
+                IniLine::Section(name) => ini.sections.push((name.to_string(), Vec::new())),
+                IniLine::Entry(key, value) => match ini.sections.last_mut() {
+                    Some(section) => section.1.push((key.to_string(), unescape(value))),
+                    None => return Err(on_line(ParseError::at(line, line.trim(), "a [section] header"))),
+                },
+            }
+        }
+
+        Ok(ini)
+    }
+
+    pub fn get(&self, section: &str, key: &str) -> Option<&str> {
+        self.sections.iter().rev()
+            .filter(|s| s.0 == section)
+            .flat_map(|s| s.1.iter().rev())
+            .find(|entry| entry.0 == key)
+            .map(|entry| entry.1.as_str())
+    }
+
+    pub fn set(&mut self, section: &str, key: &str, value: &str) {
+        if !self.sections.iter().any(|s| s.0 == section) {
+            self.sections.push((section.to_string(), Vec::new()));
+        }
+
+        let entries = &mut self.sections.iter_mut().rev().find(|s| s.0 == section).unwrap().1;
+        match entries.iter_mut().find(|entry| entry.0 == key) {
+            Some(entry) => entry.1 = value.to_string(),
+            None => entries.push((key.to_string(), value.to_string())),
+        }
+    }
+
+    pub fn section_names(&self) -> Vec<&str> {
+        self.sections.iter().fold(Vec::new(), |mut names, s| {
+            if !names.contains(&s.0.as_str()) {
+                names.push(s.0.as_str());
+            }
+            names
+        })
+    }
+
+    pub fn keys(&self, section: &str) -> Vec<&str> {
+        self.sections.iter().filter(|s| s.0 == section).flat_map(|s| s.1.iter().map(|entry| entry.0.as_str())).collect()
+    }
+}
+
+impl fmt::Display for Ini {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, (name, entries)) in self.sections.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            writeln!(f, "[{}]", name)?;
+            for (key, value) in entries {
+                writeln!(f, "{} = {}", key, escape(value))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn parse_sections() {
+    let ini = Ini::parse("# answers\n[day_1/input.txt]\npart_1 = 236\npart_2=182\n\n[day_8/input.txt]\npart_2 = #.\\n.#\n").unwrap();
+    assert_eq!(Some("236"), ini.get("day_1/input.txt", "part_1"));
+    assert_eq!(Some("182"), ini.get("day_1/input.txt", "part_2"));
+    assert_eq!(Some("#.\n.#"), ini.get("day_8/input.txt", "part_2"));
+    assert_eq!(None, ini.get("day_8/input.txt", "part_1"));
+    assert_eq!(vec!("day_1/input.txt", "day_8/input.txt"), ini.section_names());
+    assert_eq!(vec!("part_1", "part_2"), ini.keys("day_1/input.txt"));
+}
+
+#[test]
+fn parse_errors() {
+    assert_eq!(Err(ParseError::new(1, 1, "part_1 = 236", "a [section] header")), Ini::parse("part_1 = 236"));
+    assert_eq!(Err(ParseError::new(2, 1, "part_1", "key = value")), Ini::parse("[day_1]\npart_1"));
+    assert_eq!(Err(ParseError::new(1, 7, "", "a closing ]")), Ini::parse("[day_1"));
+}
+
+#[test]
+fn round_trip() {
+    let mut ini = Ini::new();
+    ini.set("day_8/input.txt", "part_2", "#.\n.#");
+    ini.set("day_1/input.txt", "part_1", "236");
+    ini.set("day_1/input.txt", "part_1", "237");
+    assert_eq!("[day_8/input.txt]\npart_2 = #.\\n.#\n\n[day_1/input.txt]\npart_1 = 237\n", ini.to_string());
+    assert_eq!(Ok(ini.clone()), Ini::parse(&ini.to_string()));
+}
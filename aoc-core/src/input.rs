@@ -0,0 +1,82 @@
+use std::env;
+use std::fmt;
+use std::fs::File;
+use std::io;
+use std::io::prelude::*;
+use std::path::{Path, PathBuf};
+use std::process;
+
+pub const DEFAULT_INPUT: &str = "input/input.txt";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+}
+
+impl InputSource {
+    // "-" reads stdin, anything else is a path, and no argument at all falls back to the default file
+    pub fn from_arg<P: AsRef<Path>>(arg: Option<&str>, default: P) -> InputSource {
+        match arg {
+            Some("-") => InputSource::Stdin,
+            Some(path) => InputSource::File(PathBuf::from(path)),
+            None => InputSource::File(default.as_ref().to_path_buf()),
+        }
+    }
+
+    pub fn read(&self) -> io::Result<String> {
+        let mut input = String::new();
+        match *self {
+            InputSource::Stdin => io::stdin().read_to_string(&mut input)?,
+            InputSource::File(ref path) => File::open(path)?.read_to_string(&mut input)?,
+        };
+        Ok(normalise(&input))
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            InputSource::Stdin => write!(f, "stdin"),
+            InputSource::File(ref path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+// Inputs arrive with CRLF endings, editor-added trailing spaces and indented test fixtures,
+// none of which any puzzle cares about
+pub fn normalise(raw: &str) -> String {
+    raw.lines().map(|line| line.trim()).collect::<Vec<&str>>().join("\n").trim_matches('\n').to_string()
+}
+
+pub fn read_input<P: AsRef<Path>>(path: P) -> io::Result<String> {
+    InputSource::File(path.as_ref().to_path_buf()).read()
+}
+
+// Reads the input named by a day binary's first argument, exiting if it can't be read
+pub fn input_from_args() -> String {
+    let source = InputSource::from_arg(env::args().nth(1).as_deref(), DEFAULT_INPUT);
+    match source.read() {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Couldn't read {}: {}", source, e);
+            process::exit(1);
+        }
+    }
+}
+
+#[test]
+fn normalised_input() {
+    assert_eq!("rect 3x2\nrotate row y=0 by 4", normalise("rect 3x2\r\nrotate row y=0 by 4\r\n"));
+    assert_eq!("rect 3x2\nrotate column x=1 by 1", normalise("rect 3x2  \n    rotate column x=1 by 1\t\n\n"));
+    assert_eq!("ULL\n\nRRDDD", normalise("\nULL\n \nRRDDD\n"));
+    assert_eq!("", normalise(" \r\n"));
+}
+
+#[test]
+fn input_sources() {
+    assert_eq!(InputSource::Stdin, InputSource::from_arg(Some("-"), DEFAULT_INPUT));
+    assert_eq!(InputSource::File(PathBuf::from("maze.txt")), InputSource::from_arg(Some("maze.txt"), DEFAULT_INPUT));
+    assert_eq!(InputSource::File(PathBuf::from(DEFAULT_INPUT)), InputSource::from_arg(None, DEFAULT_INPUT));
+    assert_eq!("stdin", InputSource::Stdin.to_string());
+}
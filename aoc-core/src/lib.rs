@@ -0,0 +1,14 @@
+pub mod differential;
+pub mod grid;
+pub mod ini;
+pub mod input;
+pub mod log;
+pub mod math;
+pub mod params;
+pub mod pattern;
+pub mod parse;
+pub mod random;
+pub mod render;
+pub mod search;
+pub mod solution;
+pub mod svg;
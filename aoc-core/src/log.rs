@@ -0,0 +1,66 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+
+// How much diagnostic output solvers write to stderr. Answers on stdout are never affected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Quiet,
+    Debug,
+    Trace,
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Quiet as u8);
+
+impl Level {
+    // One `-v` turns on debug output, two or more turn on tracing
+    pub fn from_verbosity(count: usize) -> Level {
+        match count {
+            0 => Level::Quiet,
+            1 => Level::Debug,
+            _ => Level::Trace,
+        }
+    }
+}
+
+pub fn set_level(level: Level) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+pub fn enabled(level: Level) -> bool {
+    level != Level::Quiet && LEVEL.load(Ordering::Relaxed) >= level as u8
+}
+
+// Progress worth seeing when something looks slow or wrong, such as a search finding a better path
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if $crate::log::enabled($crate::log::Level::Debug) {
+            eprintln!("[debug] {}", format_args!($($arg)*));
+        }
+    };
+}
+
+// Per-step state, far too much for anything but a small input
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if $crate::log::enabled($crate::log::Level::Trace) {
+            eprintln!("[trace] {}", format_args!($($arg)*));
+        }
+    };
+}
+
+#[test]
+fn levels() {
+    assert_eq!(Level::Quiet, Level::from_verbosity(0));
+    assert_eq!(Level::Debug, Level::from_verbosity(1));
+    assert_eq!(Level::Trace, Level::from_verbosity(3));
+
+    assert!(!enabled(Level::Debug));
+    set_level(Level::Debug);
+    assert!(enabled(Level::Debug));
+    assert!(!enabled(Level::Trace));
+    set_level(Level::Trace);
+    assert!(enabled(Level::Debug) && enabled(Level::Trace));
+    set_level(Level::Quiet);
+    assert!(!enabled(Level::Quiet));
+}
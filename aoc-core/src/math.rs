@@ -0,0 +1,26 @@
+pub fn modulo(x: i32, d: i32) -> i32 {
+    x.rem_euclid(d)
+}
+
+pub fn char_to_index(c: char) -> usize {
+    (c as u32 - 'a' as u32) as usize
+}
+
+pub fn index_to_char(i: usize) -> char {
+    (i as u8 + b'a') as char
+}
+
+#[test]
+fn modulo_wraps_negatives() {
+    assert_eq!(3, modulo(-4, 7));
+    assert_eq!(0, modulo(7, 7));
+    assert_eq!(2, modulo(9, 7));
+    assert_eq!(0, modulo(-7, 7));
+}
+
+#[test]
+fn char_index_round_trip() {
+    assert_eq!(0, char_to_index('a'));
+    assert_eq!(25, char_to_index('z'));
+    assert_eq!('q', index_to_char(char_to_index('q')));
+}
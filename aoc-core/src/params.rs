@@ -0,0 +1,216 @@
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use crate::ini::{parse_line, IniLine};
+use crate::input::InputSource;
+use crate::parse::ParseError;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Param {
+    file: Option<String>,
+    key: String,
+    line: String,
+    line_number: usize,
+    value_start: usize,
+}
+
+impl Param {
+    fn value(&self) -> &str {
+        &self.line[self.value_start..self.line.trim_end().len()]
+    }
+
+    fn error(&self, token: &str, expected: &str) -> ParseError {
+        let error = ParseError::at(&self.line, token, &format!("{} for {}", expected, self.key)).on_line(self.line_number);
+        match self.file {
+            Some(ref file) => error.in_file(file),
+            None => error,
+        }
+    }
+}
+
+// Puzzle parameters read from a `key = value` file that sits next to an input, e.g.
+// `target = 31,39` in day_13/input/input.ini. Days fall back to their own defaults for missing keys.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Params {
+    params: Vec<Param>,
+    // The .ini file the parameters came from, or would have come from if the input had one
+    file: Option<String>,
+}
+
+impl Params {
+    pub fn new() -> Params {
+        Params::default()
+    }
+
+    pub fn parse(text: &str) -> Result<Params, ParseError> {
+        let mut params = Params::new();
+
+        for (line_number, line) in text.lines().enumerate() {
+            match parse_line(line).map_err(|e| e.on_line(line_number + 1))? {
+                IniLine::Entry(key, value) => params.params.push(Param {
+                    file: None,
+                    key: key.to_string(),
+                    line: line.to_string(),
+                    line_number: line_number + 1,
+                    value_start: value.as_ptr() as usize - line.as_ptr() as usize,
+                }),
+                IniLine::Section(name) => return Err(ParseError::at(line, name, "key = value").on_line(line_number + 1)),
+                IniLine::Blank => {},
+            }
+        }
+
+        Ok(params)
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Params, String> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).map_err(|e| format!("Couldn't read {}: {}", path.display(), e))?;
+        let file = path.display().to_string();
+        let mut params = Params::parse(&text).map_err(|e| e.in_file(&file).to_string())?;
+        for param in params.params.iter_mut() {
+            param.file = Some(file.clone());
+        }
+        params.file = Some(file);
+        Ok(params)
+    }
+
+    // The parameters for an input file are in a .ini file of the same name, if there is one
+    pub fn for_input(source: &InputSource) -> Result<Params, String> {
+        match *source {
+            InputSource::File(ref path) if path.with_extension("ini").is_file() => Params::load(path.with_extension("ini")),
+            InputSource::File(ref path) => Ok(Params { file: Some(path.with_extension("ini").display().to_string()), ..Params::new() }),
+            _ => Ok(Params::new()),
+        }
+    }
+
+    // Mostly for tests, where a one-off value is easier than a file
+    pub fn with(mut self, key: &str, value: &str) -> Params {
+        let line = format!("{} = {}", key, value);
+        self.params.push(Param { file: None, key: key.to_string(), value_start: key.len() + 3, line, line_number: 1 });
+        self
+    }
+
+    fn find(&self, key: &str) -> Option<&Param> {
+        self.params.iter().rev().find(|param| param.key == key)
+    }
+
+    pub fn is_set(&self, key: &str) -> bool {
+        self.find(key).is_some()
+    }
+
+    pub fn get<T: FromStr>(&self, key: &str, default: T) -> Result<T, ParseError> {
+        match self.find(key) {
+            Some(param) => param.value().parse().map_err(|_| param.error(param.value(), "a value")),
+            None => Ok(default),
+        }
+    }
+
+    pub fn get_str<'a>(&'a self, key: &str, default: &'a str) -> &'a str {
+        self.find(key).map(|param| param.value()).unwrap_or(default)
+    }
+
+    // For a value that parsed but doesn't fit the puzzle, such as a target square inside a wall.
+    // Blames the parameter where it was set, or asks for it to be set in the .ini file if the day's
+    // default was used.
+    pub fn reject(&self, key: &str, expected: &str) -> ParseError {
+        if let Some(param) = self.find(key) {
+            return param.error(param.value(), expected);
+        }
+        let error = ParseError::new(1, 1, "", &format!("{} for {}, which isn't set", expected, key));
+        match self.file {
+            Some(ref file) => error.in_file(file),
+            None => error,
+        }
+    }
+
+    // Some parameters name a file, such as day 2's keypad drawings. Paths are relative to the
+    // parameter file they came from. Returns the path, for reporting errors in the file, and its text.
+    pub fn read_file(&self, key: &str) -> Result<Option<(String, String)>, ParseError> {
+        let param = match self.find(key) {
+            Some(param) => param,
+            None => return Ok(None),
+        };
+
+        let path = match param.file {
+            Some(ref file) => Path::new(file).parent().unwrap_or(Path::new("")).join(param.value()),
+            None => Path::new(param.value()).to_path_buf(),
+        };
+        match fs::read_to_string(&path) {
+            Ok(text) => Ok(Some((path.display().to_string(), text))),
+            Err(_) => Err(param.error(param.value(), "a readable file")),
+        }
+    }
+
+    // Pairs are written `x,y`
+    pub fn get_pair<T: FromStr>(&self, key: &str, default: (T, T)) -> Result<(T, T), ParseError> {
+        let param = match self.find(key) {
+            Some(param) => param,
+            None => return Ok(default),
+        };
+
+        let value = param.value();
+        let parts = value.split(',').map(|part| part.trim()).collect::<Vec<&str>>();
+        match parts.len() {
+            2 => {
+                let parse_part = |part: &str| -> Result<T, ParseError> {
+                    part.parse().map_err(|_| param.error(part, "a pair like 31,39"))
+                };
+                Ok((parse_part(parts[0])?, parse_part(parts[1])?))
+            },
+            _ => Err(param.error(value, "a pair like 31,39")),
+        }
+    }
+}
+
+#[test]
+fn params_from_text() {
+    let params = Params::parse("# day 13\ntarget = 31, 39\nmax_steps = 50\npassword = abcdefgh  \n").unwrap();
+    assert_eq!(Ok((31, 39)), params.get_pair("target", (7, 4)));
+    assert_eq!(Ok(50), params.get("max_steps", 10));
+    assert_eq!(Ok(2503), params.get("race_length", 2503));
+    assert_eq!("abcdefgh", params.get_str("password", "abcde"));
+    assert_eq!("fbgdceah", params.get_str("scrambled", "fbgdceah"));
+}
+
+#[test]
+fn bad_params() {
+    let params = Params::parse("max_steps = fifty\ntarget = 31;39\nstart = 1,y").unwrap();
+    assert_eq!(Err(ParseError::new(1, 13, "fifty", "a value for max_steps")), params.get("max_steps", 50));
+    assert_eq!(Err(ParseError::new(2, 10, "31;39", "a pair like 31,39 for target")), params.get_pair("target", (31, 39)));
+    assert_eq!(Err(ParseError::new(3, 11, "y", "a pair like 31,39 for start")), params.get_pair("start", (1, 1)));
+    assert_eq!(Err(ParseError::new(1, 2, "puzzle", "key = value")), Params::parse("[puzzle]"));
+    assert_eq!(ParseError::new(2, 10, "31;39", "an open square for target"), params.reject("target", "an open square"));
+    assert_eq!(ParseError::new(1, 1, "", "an open square for goal, which isn't set"), params.reject("goal", "an open square"));
+    assert!(params.is_set("target"));
+    assert!(!params.is_set("goal"));
+}
+
+#[test]
+fn params_from_code() {
+    let params = Params::new().with("screen_size", "7,3").with("race_length", "1000");
+    assert_eq!(Ok((7, 3)), params.get_pair("screen_size", (50, 6)));
+    assert_eq!(Ok(1000), params.get("race_length", 2503));
+}
+
+#[test]
+fn files_next_to_params() {
+    let dir = std::env::temp_dir().join(format!("aoc-params-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("input.ini"), "keypad = keypad.txt\nmissing = nowhere.txt").unwrap();
+    fs::write(dir.join("keypad.txt"), " 1\n234").unwrap();
+
+    let params = Params::load(dir.join("input.ini")).unwrap();
+    assert_eq!(Ok(Some((dir.join("keypad.txt").display().to_string(), " 1\n234".to_string()))), params.read_file("keypad"));
+    assert_eq!(Ok(None), params.read_file("layout"));
+    let error = params.read_file("missing").unwrap_err();
+    assert_eq!((2, 11, "nowhere.txt", "a readable file for missing"), (error.line, error.column, error.token.as_str(), error.expected.as_str()));
+    let ini = dir.join("input.ini").display().to_string();
+    assert_eq!(Some(ini.clone()), params.reject("layout", "a keypad").file);
+
+    // Without a .ini file, an unset parameter is blamed on the one the input would have
+    let params = Params::for_input(&InputSource::File(dir.join("other.txt"))).unwrap();
+    assert_eq!(Some(dir.join("other.ini").display().to_string()), params.reject("layout", "a keypad").file);
+    assert_eq!(Some(ini), Params::for_input(&InputSource::File(dir.join("input.txt"))).unwrap().reject("layout", "a keypad").file);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
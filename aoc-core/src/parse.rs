@@ -0,0 +1,143 @@
+use std::cmp;
+use std::error::Error;
+use std::fmt;
+use std::process;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub token: String,
+    pub expected: String,
+    // Set when the error is in a file other than the puzzle input, such as a parameter file
+    pub file: Option<String>,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, token: &str, expected: &str) -> ParseError {
+        ParseError { line, column, token: token.to_string(), expected: expected.to_string(), file: None }
+    }
+
+    // Builds an error for a token sliced out of `line`, working out the column from where the
+    // slice sits. Errors start out on line 1 and are moved with `on_line` by whoever knows better.
+    pub fn at(line: &str, token: &str, expected: &str) -> ParseError {
+        let line_start = line.as_ptr() as usize;
+        let token_start = token.as_ptr() as usize;
+        let offset = if token_start >= line_start && token_start <= line_start + line.len() {
+            token_start - line_start
+        } else {
+            line.find(token).unwrap_or(line.len())
+        };
+
+        ParseError::new(1, line[..offset].chars().count() + 1, token, expected)
+    }
+
+    // An error for something missing from the end of `line`
+    pub fn at_end(line: &str, expected: &str) -> ParseError {
+        ParseError::at(line, &line[line.len()..], expected)
+    }
+
+    // An error for something missing from the end of a whole input, such as a rule that nothing
+    // in it ever satisfies
+    pub fn at_end_of_input(input: &str, expected: &str) -> ParseError {
+        let lines = input.lines().collect::<Vec<&str>>();
+        ParseError::at_end(lines.last().copied().unwrap_or(""), expected).on_line(cmp::max(1, lines.len()))
+    }
+
+    pub fn on_line(self, line: usize) -> ParseError {
+        ParseError { line, ..self }
+    }
+
+    pub fn in_file(self, file: &str) -> ParseError {
+        ParseError { file: Some(file.to_string()), ..self }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let found = match self.token.is_empty() {
+            true => "end of line".to_string(),
+            false => format!("{:?}", self.token)
+        };
+        if let Some(ref file) = self.file {
+            write!(f, "{}: ", file)?;
+        }
+        write!(f, "line {}, column {}: expected {}, found {}", self.line, self.column, self.expected, found)
+    }
+}
+
+impl Error for ParseError {}
+
+// Parses a token sliced out of `line`, blaming it if it isn't what was expected
+pub fn token<T: FromStr>(line: &str, token: &str, expected: &str) -> Result<T, ParseError> {
+    token.parse().map_err(|_| ParseError::at(line, token, expected))
+}
+
+// Fetches one of the fields `line` was split into, blaming the end of the line if it's missing
+pub fn field<'a>(line: &'a str, fields: &[&'a str], index: usize, expected: &str) -> Result<&'a str, ParseError> {
+    fields.get(index).cloned().ok_or_else(|| ParseError::at_end(line, expected))
+}
+
+// Runs a single line parser over every line of the input, keeping track of line numbers
+pub fn parse_lines<T, F>(input: &str, parse_line: F) -> Result<Vec<T>, ParseError>
+    where F: Fn(&str) -> Result<T, ParseError> {
+    input.lines().enumerate().map(|(i, line)| parse_line(line).map_err(|e| e.on_line(i + 1))).collect()
+}
+
+// Reports a parse failure and exits, for binaries that have nothing better to do with one
+pub fn or_exit<T>(result: Result<T, ParseError>) -> T {
+    result.unwrap_or_else(|e| {
+        eprintln!("Couldn't parse input: {}", e);
+        process::exit(1);
+    })
+}
+
+// Pulls every (optionally negative) integer out of a line, ignoring the words around them.
+pub fn integers<T: FromStr>(line: &str) -> Vec<T> {
+    line.split(|c: char| !(c.is_ascii_digit() || c == '-'))
+        .filter(|s| s.chars().any(|c| c.is_ascii_digit()))
+        .filter_map(|s| s.parse().ok())
+        .collect()
+}
+
+#[test]
+fn integers_from_text() {
+    assert_eq!(vec!(5, 10, 25), integers::<i32>("  5   10   25"));
+    assert_eq!(vec!(5, -1, 0, 0, 5), integers::<i32>("Sprinkles: capacity 5, durability -1, flavor 0, texture 0, calories 5"));
+    assert!(integers::<i32>("rect axb").is_empty());
+}
+
+#[test]
+fn error_columns() {
+    let line = "rotate row y=0 bye 4";
+    let split = line.split_whitespace().collect::<Vec<&str>>();
+    assert_eq!(ParseError::new(1, 16, "bye", "\"by\""), ParseError::at(line, split[3], "\"by\""));
+    assert_eq!(ParseError::new(1, 21, "", "a distance"), ParseError::at_end(line, "a distance"));
+    assert_eq!(ParseError::new(3, 8, "row", "a thing"), ParseError::at(line, "row", "a thing").on_line(3));
+    assert_eq!(ParseError::new(2, 4, "", "a fourth"), ParseError::at_end_of_input("first\nrow\n", "a fourth"));
+    assert_eq!(ParseError::new(1, 1, "", "a first"), ParseError::at_end_of_input("", "a first"));
+}
+
+#[test]
+fn error_message() {
+    assert_eq!("line 2, column 5: expected a number, found \"x\"", ParseError::new(2, 5, "x", "a number").to_string());
+    assert_eq!("line 1, column 9: expected a number, found end of line", ParseError::new(1, 9, "", "a number").to_string());
+    assert_eq!("maze.ini: line 1, column 9: expected a pair, found \"x\"", ParseError::new(1, 9, "x", "a pair").in_file("maze.ini").to_string());
+}
+
+#[test]
+fn tokens_and_fields() {
+    let line = "cpy 41 a";
+    let split = line.split_whitespace().collect::<Vec<&str>>();
+    assert_eq!(Ok(41), token::<i32>(line, split[1], "a value"));
+    assert_eq!(Err(ParseError::new(1, 8, "a", "a value")), token::<i32>(line, split[2], "a value"));
+    assert_eq!(Ok("a"), field(line, &split, 2, "a register"));
+    assert_eq!(Err(ParseError::new(1, 9, "", "a register")), field(line, &split, 3, "a register"));
+}
+
+#[test]
+fn line_numbers() {
+    let parsed = parse_lines("1\n2\nthree", |line| token::<i32>(line, line, "a number"));
+    assert_eq!(Err(ParseError::new(3, 1, "three", "a number")), parsed);
+}
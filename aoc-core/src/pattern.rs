@@ -0,0 +1,207 @@
+use crate::parse::{ParseError, token};
+
+// A line format such as "bot {a bot number} gives low to {bot or output} {a bot or output number}".
+// Words are separated by whitespace. Each {capture} holds a description of what belongs there, used
+// in errors, and the words around captures have to appear exactly. Literal words can allow
+// alternatives separated by '|', such as "step|steps".
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pattern {
+    words: Vec<Vec<Piece>>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Piece {
+    Text(Vec<String>),
+    Capture(String),
+}
+
+// Turns the text of one capture into a value, blaming the capture with `expected` when it can't
+pub trait FromCapture<'a>: Sized {
+    fn from_capture(line: &'a str, text: &'a str, expected: &str) -> Result<Self, ParseError>;
+}
+
+// Everything a pattern captured, in order
+pub trait FromCaptures<'a>: Sized {
+    fn from_captures(line: &'a str, captures: &[(&'a str, &str)]) -> Result<Self, ParseError>;
+}
+
+macro_rules! from_str_captures {
+    ($($t:ty),*) => {
+        $(impl<'a> FromCapture<'a> for $t {
+            fn from_capture(line: &'a str, text: &'a str, expected: &str) -> Result<$t, ParseError> {
+                token(line, text, expected)
+            }
+        })*
+    };
+}
+
+from_str_captures!(i32, i64, u16, u32, u64, usize, char, String);
+
+impl<'a> FromCapture<'a> for &'a str {
+    fn from_capture(_line: &'a str, text: &'a str, _expected: &str) -> Result<&'a str, ParseError> {
+        Ok(text)
+    }
+}
+
+impl<'a> FromCaptures<'a> for () {
+    fn from_captures(_line: &'a str, _captures: &[(&'a str, &str)]) -> Result<(), ParseError> {
+        Ok(())
+    }
+}
+
+impl<'a, A: FromCapture<'a>> FromCaptures<'a> for A {
+    fn from_captures(line: &'a str, captures: &[(&'a str, &str)]) -> Result<A, ParseError> {
+        A::from_capture(line, captures[0].0, captures[0].1)
+    }
+}
+
+macro_rules! tuple_captures {
+    ($($t:ident $i:tt),*) => {
+        impl<'a, $($t: FromCapture<'a>),*> FromCaptures<'a> for ($($t,)*) {
+            fn from_captures(line: &'a str, captures: &[(&'a str, &str)]) -> Result<($($t,)*), ParseError> {
+                Ok(($($t::from_capture(line, captures[$i].0, captures[$i].1)?,)*))
+            }
+        }
+    };
+}
+
+tuple_captures!(A 0, B 1);
+tuple_captures!(A 0, B 1, C 2);
+tuple_captures!(A 0, B 1, C 2, D 3);
+tuple_captures!(A 0, B 1, C 2, D 3, E 4);
+
+impl Pattern {
+    // Panics on a template with unbalanced braces, which is a mistake in the code rather than the input
+    pub fn new(template: &str) -> Pattern {
+        let mut words = vec![Vec::new()];
+        let mut text = String::new();
+        let mut capture: Option<String> = None;
+
+        for c in template.chars() {
+            match (c, capture.as_mut()) {
+                ('}', Some(description)) => {
+                    words.last_mut().unwrap().push(Piece::Capture(description.clone()));
+                    capture = None;
+                },
+                ('{', Some(_)) | ('}', None) => panic!("Unbalanced braces in {:?}", template),
+                (c, Some(description)) => description.push(c),
+                ('{', None) => {
+                    Pattern::push_text(words.last_mut().unwrap(), &mut text);
+                    capture = Some(String::new());
+                },
+                (c, None) if c.is_whitespace() => {
+                    Pattern::push_text(words.last_mut().unwrap(), &mut text);
+                    words.push(Vec::new());
+                },
+                (c, None) => text.push(c),
+            }
+        }
+        assert!(capture.is_none(), "Unclosed capture in {:?}", template);
+        Pattern::push_text(words.last_mut().unwrap(), &mut text);
+        words.retain(|pieces| !pieces.is_empty());
+
+        // Captures inside a word need some text after them to know where they end
+        assert!(words.iter().all(|pieces| pieces.windows(2).all(|pair| matches!(pair[0], Piece::Text(_)) || matches!(pair[1], Piece::Text(_)))),
+                "Neighbouring captures in {:?}", template);
+        Pattern { words }
+    }
+
+    fn push_text(pieces: &mut Vec<Piece>, text: &mut String) {
+        if !text.is_empty() {
+            pieces.push(Piece::Text(text.split('|').map(|choice| choice.to_string()).collect()));
+            text.clear();
+        }
+    }
+
+    // Matches the whole line and converts the captures, such as into an (i32, usize) tuple
+    pub fn parse<'a, T: FromCaptures<'a>>(&self, line: &'a str) -> Result<T, ParseError> {
+        T::from_captures(line, &self.captures(line)?)
+    }
+
+    pub fn matches(&self, line: &str) -> bool {
+        self.captures(line).is_ok()
+    }
+
+    fn captures<'a, 'p>(&'p self, line: &'a str) -> Result<Vec<(&'a str, &'p str)>, ParseError> {
+        let mut captures = Vec::new();
+        let mut words = line.split_whitespace();
+
+        for (index, pieces) in self.words.iter().enumerate() {
+            let word = words.next().ok_or_else(|| ParseError::at_end(line, &self.missing(index)))?;
+            let mut rest = word;
+            for (i, piece) in pieces.iter().enumerate() {
+                match piece {
+                    Piece::Text(choices) => {
+                        let text = choices.iter().find(|text| rest.starts_with(text.as_str()) && (i + 1 < pieces.len() || rest.len() == text.len()));
+                        match text {
+                            Some(text) => rest = &rest[text.len()..],
+                            None => return Err(ParseError::at(line, word, &Pattern::describe(pieces))),
+                        }
+                    },
+                    Piece::Capture(description) => {
+                        let end = match pieces.get(i + 1) {
+                            Some(Piece::Text(choices)) => choices.iter().filter_map(|text| rest.find(text.as_str())).min(),
+                            _ => Some(rest.len()),
+                        };
+                        let end = end.ok_or_else(|| ParseError::at(line, word, description))?;
+                        captures.push((&rest[..end], description.as_str()));
+                        rest = &rest[end..];
+                    }
+                }
+            }
+        }
+
+        match words.next() {
+            Some(extra) => Err(ParseError::at(line, extra, "the end of the line")),
+            None => Ok(captures),
+        }
+    }
+
+    // What a word should look like: the first thing captured in it, or its text
+    fn describe(pieces: &[Piece]) -> String {
+        let capture = pieces.iter().find_map(|piece| match piece {
+            Piece::Capture(description) => Some(description.clone()),
+            Piece::Text(_) => None,
+        });
+        capture.unwrap_or_else(|| {
+            let choices = pieces.iter().flat_map(|piece| match piece {
+                Piece::Text(choices) => choices.iter().map(|text| format!("{:?}", text)).collect(),
+                Piece::Capture(_) => vec![],
+            }).collect::<Vec<String>>();
+            choices.join(" or ")
+        })
+    }
+
+    // A line that stops short is missing the next thing to be captured more than the words before it
+    fn missing(&self, index: usize) -> String {
+        self.words[index..].iter().find(|pieces| pieces.iter().any(|piece| matches!(piece, Piece::Capture(_))))
+            .map_or_else(|| Pattern::describe(&self.words[index]), |pieces| Pattern::describe(pieces))
+    }
+}
+
+#[test]
+fn typed_captures() {
+    let pattern = Pattern::new("bot {a bot number} gives low to {bot or output} {a number}");
+    assert_eq!(Ok((2, "output", 7)), pattern.parse::<(i32, &str, u32)>("bot 2 gives low to output 7"));
+    assert_eq!(Ok('x'), Pattern::new("letter {a letter}").parse::<char>("letter x"));
+    assert_eq!(Ok(()), Pattern::new("noop").parse::<()>("  noop "));
+    assert_eq!(Ok((3, 2)), Pattern::new("rect {a width}x{a height}").parse::<(usize, usize)>("rect 3x2"));
+    assert_eq!(Ok(4), Pattern::new("row y={a row} by {a distance} step|steps").parse::<(usize, i32)>("row y=4 by 1 step").map(|(row, _)| row));
+    assert!(Pattern::new("rotate {a distance} step|steps").matches("rotate 2 steps"));
+    assert!(!Pattern::new("rotate {a distance} step|steps").matches("rotate 2 stepss"));
+}
+
+#[test]
+fn capture_errors() {
+    let pattern = Pattern::new("value {a microchip value} goes to bot {a bot number}");
+    assert_eq!(Err(ParseError::new(1, 7, "two", "a microchip value")), pattern.parse::<(i32, i32)>("value two goes to bot 2"));
+    assert_eq!(Err(ParseError::new(1, 9, "comes", "\"goes\"")), pattern.parse::<(i32, i32)>("value 5 comes to bot 2"));
+    assert_eq!(Err(ParseError::new(1, 20, "", "a bot number")), pattern.parse::<(i32, i32)>("value 5 goes to bot"));
+    assert_eq!(Err(ParseError::new(1, 16, "", "a bot number")), pattern.parse::<(i32, i32)>("value 5 goes to"));
+    assert_eq!(Err(ParseError::new(1, 23, "3", "the end of the line")), pattern.parse::<(i32, i32)>("value 5 goes to bot 2 3"));
+
+    let pattern = Pattern::new("row y={a row} by {a distance} step|steps");
+    assert_eq!(Err(ParseError::new(1, 5, "x=1", "a row")), pattern.parse::<(usize, i32)>("row x=1 by 1 step"));
+    assert_eq!(Err(ParseError::new(1, 13, "", "\"step\" or \"steps\"")), pattern.parse::<(usize, i32)>("row y=1 by 1"));
+    assert_eq!(Err(ParseError::new(1, 6, "3by2", "a width")), Pattern::new("rect {a width}x{a height}").parse::<(usize, usize)>("rect 3by2"));
+}
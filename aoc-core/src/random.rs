@@ -0,0 +1,77 @@
+// A small seeded generator (splitmix64) so a seed produces the same puzzle input on every machine
+// and every build, without pulling in a dependency whose output might change between versions
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // Uniform in `0..n`
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "Can't pick a number below 0");
+        (self.next_u64() % n as u64) as usize
+    }
+
+    // Uniform in `low..=high`
+    pub fn range(&mut self, low: i64, high: i64) -> i64 {
+        low + self.below((high - low + 1) as usize) as i64
+    }
+
+    pub fn one_in(&mut self, n: usize) -> bool {
+        self.below(n) == 0
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+
+    // `length` characters drawn from `alphabet`
+    pub fn word(&mut self, alphabet: &str, length: usize) -> String {
+        let letters = alphabet.chars().collect::<Vec<char>>();
+        (0..length).map(|_| *self.pick(&letters)).collect()
+    }
+}
+
+pub const LOWERCASE: &str = "abcdefghijklmnopqrstuvwxyz";
+
+#[test]
+fn seeded() {
+    let (mut a, mut b) = (Rng::new(2016), Rng::new(2016));
+    assert_eq!((0..10).map(|_| a.next_u64()).collect::<Vec<u64>>(), (0..10).map(|_| b.next_u64()).collect::<Vec<u64>>());
+    assert!(Rng::new(1).next_u64() != Rng::new(2).next_u64());
+}
+
+#[test]
+fn ranges() {
+    let mut rng = Rng::new(7);
+    for _ in 0..1000 {
+        assert!(rng.below(6) < 6);
+        assert!((-3..=3).contains(&rng.range(-3, 3)));
+    }
+    assert!((0..1000).any(|_| rng.range(-3, 3) == 3));
+
+    let mut items = (0..20).collect::<Vec<u32>>();
+    rng.shuffle(&mut items);
+    assert!(items != (0..20).collect::<Vec<u32>>());
+    items.sort();
+    assert_eq!((0..20).collect::<Vec<u32>>(), items);
+    assert_eq!(5, rng.word("ab", 5).len());
+}
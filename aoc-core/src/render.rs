@@ -0,0 +1,137 @@
+use std::io;
+use std::io::{IsTerminal, Write};
+use std::time::{Duration, Instant};
+
+const CURSOR_UP: &str = "\x1b[{}A";
+const CLEAR_BELOW: &str = "\x1b[J";
+const HIDE_CURSOR: &str = "\x1b[?25l";
+const SHOW_CURSOR: &str = "\x1b[?25h";
+
+// Draws animation frames over one another in place. Frames that arrive faster than the frame rate
+// are skipped, apart from the last one. Without a terminal to draw on, nothing is shown until
+// `finish`, which prints only the final frame. Frames are built by closures that only run when the
+// frame is actually drawn, so skipping costs nothing even for animations with millions of steps.
+pub struct Renderer<W: Write> {
+    out: W,
+    interactive: bool,
+    frame_interval: Duration,
+    last_drawn: Option<Instant>,
+    height: usize,
+    // Whether a frame has been skipped since the last one drawn
+    behind: bool,
+}
+
+impl Renderer<io::Stdout> {
+    // `fps` of 0 draws every frame
+    pub fn stdout(fps: u32) -> Renderer<io::Stdout> {
+        let interactive = io::stdout().is_terminal();
+        Renderer::new(io::stdout(), interactive, fps)
+    }
+}
+
+impl<W: Write> Renderer<W> {
+    pub fn new(out: W, interactive: bool, fps: u32) -> Renderer<W> {
+        let frame_interval = match fps {
+            0 => Duration::from_secs(0),
+            fps => Duration::from_secs(1) / fps,
+        };
+        Renderer { out, interactive, frame_interval, last_drawn: None, height: 0, behind: false }
+    }
+
+    pub fn frame<F: FnOnce() -> String>(&mut self, build: F) {
+        let due = self.interactive && self.last_drawn.is_none_or(|last| last.elapsed() >= self.frame_interval);
+        match due {
+            true => self.draw(&build()),
+            false => self.behind = true,
+        }
+    }
+
+    // Shows the final frame, built by `last`, if the last one was skipped, and hands the terminal back
+    pub fn finish<F: FnOnce() -> String>(&mut self, last: F) {
+        if self.behind {
+            self.draw(&last());
+        }
+        if self.interactive && self.last_drawn.is_some() {
+            write!(self.out, "{}", SHOW_CURSOR).expect("Couldn't draw a frame");
+        }
+        self.out.flush().expect("Couldn't draw a frame");
+        self.last_drawn = None;
+        self.height = 0;
+    }
+
+    // Whether frames are drawn as they come, for callers that pace an animation themselves
+    pub fn is_interactive(&self) -> bool {
+        self.interactive
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+
+    fn draw(&mut self, text: &str) {
+        let mut frame = String::new();
+        if self.interactive {
+            match self.height {
+                0 => frame.push_str(HIDE_CURSOR),
+                height => frame.push_str(&CURSOR_UP.replace("{}", &height.to_string())),
+            }
+            frame.push_str(CLEAR_BELOW);
+        }
+        frame.push_str(text);
+        frame.push('\n');
+
+        self.out.write_all(frame.as_bytes()).and_then(|_| self.out.flush()).expect("Couldn't draw a frame");
+        self.height = text.lines().count();
+        self.last_drawn = Some(Instant::now());
+        self.behind = false;
+    }
+}
+
+#[cfg(test)]
+fn rendered(renderer: Renderer<Vec<u8>>) -> String {
+    String::from_utf8(renderer.into_inner()).unwrap()
+}
+
+#[test]
+fn redraws_in_place() {
+    let mut renderer = Renderer::new(Vec::new(), true, 0);
+    renderer.frame(|| "#.\n.#".to_string());
+    renderer.frame(|| ".#\n#.".to_string());
+    renderer.finish(|| panic!("The last frame was already drawn"));
+    assert_eq!("\x1b[?25l\x1b[J#.\n.#\n\x1b[2A\x1b[J.#\n#.\n\x1b[?25h", rendered(renderer));
+}
+
+#[test]
+fn limits_frame_rate() {
+    let mut renderer = Renderer::new(Vec::new(), true, 1);
+    renderer.frame(|| "one".to_string());
+    renderer.frame(|| "two".to_string());
+    renderer.frame(|| "three".to_string());
+    renderer.finish(|| "three".to_string());
+    assert_eq!("\x1b[?25l\x1b[Jone\n\x1b[1A\x1b[Jthree\n\x1b[?25h", rendered(renderer));
+}
+
+#[test]
+fn plain_output_without_a_terminal() {
+    let mut renderer = Renderer::new(Vec::new(), false, 0);
+    renderer.frame(|| "#.\n.#".to_string());
+    renderer.frame(|| ".#\n#.".to_string());
+    renderer.finish(|| ".#\n#.".to_string());
+    assert_eq!(".#\n#.\n", rendered(renderer));
+}
+
+#[test]
+fn builds_only_drawn_frames() {
+    let mut built = 0;
+    let mut renderer = Renderer::new(Vec::new(), false, 0);
+    for _ in 0..1000 {
+        renderer.frame(|| { built += 1; "frame".to_string() });
+    }
+    renderer.finish(|| "last".to_string());
+    assert_eq!(0, built);
+    assert_eq!("last\n", rendered(renderer));
+
+    let mut renderer = Renderer::new(Vec::new(), false, 0);
+    renderer.finish(|| panic!("Nothing was animated"));
+    assert_eq!("", rendered(renderer));
+}
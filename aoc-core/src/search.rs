@@ -0,0 +1,266 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+// Wraps an item with a score so that a BinaryHeap pops the lowest score first.
+// Only the score takes part in comparisons.
+#[derive(Clone, Debug)]
+pub struct MinScored<K, T>(pub K, pub T);
+
+impl<K: Ord, T> Eq for MinScored<K, T> {}
+
+impl<K: Ord, T> PartialEq for MinScored<K, T> {
+    fn eq(&self, other: &MinScored<K, T>) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<K: Ord, T> PartialOrd for MinScored<K, T> {
+    fn partial_cmp(&self, other: &MinScored<K, T>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K: Ord, T> Ord for MinScored<K, T> {
+    fn cmp(&self, other: &MinScored<K, T>) -> Ordering {
+        other.0.cmp(&self.0)
+    }
+}
+
+// The states a search passed through, start and goal included, and what it cost to get there.
+// For the unweighted searches the cost is the number of steps.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Path<S> {
+    pub states: Vec<S>,
+    pub cost: usize,
+}
+
+// Follows the links each search keeps from a state back to the one it was reached from
+fn walk_back<S: Hash + Eq + Clone>(parents: &HashMap<S, S>, end: S) -> Vec<S> {
+    let mut states = vec![end];
+    while let Some(parent) = parents.get(states.last().unwrap()) {
+        states.push(parent.clone());
+    }
+    states.reverse();
+    states
+}
+
+pub fn bfs<S, N, I, G>(start: S, neighbours: N, is_goal: G) -> Option<Path<S>>
+    where S: Hash + Eq + Clone, N: FnMut(&S) -> I, I: IntoIterator<Item = S>, G: FnMut(&S) -> bool {
+    bfs_within(start, neighbours, is_goal, usize::MAX)
+}
+
+// A breadth-first search that gives up on goals more than `max_depth` steps away
+pub fn bfs_within<S, N, I, G>(start: S, mut neighbours: N, mut is_goal: G, max_depth: usize) -> Option<Path<S>>
+    where S: Hash + Eq + Clone, N: FnMut(&S) -> I, I: IntoIterator<Item = S>, G: FnMut(&S) -> bool {
+    let mut parents = HashMap::new();
+    let mut depths = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(state) = queue.pop_front() {
+        let depth = depths[&state];
+        if is_goal(&state) {
+            return Some(Path { states: walk_back(&parents, state), cost: depth });
+        }
+        if depth == max_depth {
+            continue;
+        }
+        for neighbour in neighbours(&state) {
+            if !depths.contains_key(&neighbour) {
+                depths.insert(neighbour.clone(), depth + 1);
+                parents.insert(neighbour.clone(), state.clone());
+                queue.push_back(neighbour);
+            }
+        }
+    }
+
+    None
+}
+
+// Every state at most `max_depth` steps from `start`, with how many steps it takes to get there
+pub fn reachable_within<S, N, I>(start: S, mut neighbours: N, max_depth: usize) -> HashMap<S, usize>
+    where S: Hash + Eq + Clone, N: FnMut(&S) -> I, I: IntoIterator<Item = S> {
+    let mut depths = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(state) = queue.pop_front() {
+        let depth = depths[&state];
+        if depth == max_depth {
+            continue;
+        }
+        for neighbour in neighbours(&state) {
+            if !depths.contains_key(&neighbour) {
+                depths.insert(neighbour.clone(), depth + 1);
+                queue.push_back(neighbour);
+            }
+        }
+    }
+
+    depths
+}
+
+// Neighbours come with the cost of the step to them
+pub fn dijkstra<S, N, I, G>(start: S, neighbours: N, is_goal: G) -> Option<Path<S>>
+    where S: Hash + Eq + Clone, N: FnMut(&S) -> I, I: IntoIterator<Item = (S, usize)>, G: FnMut(&S) -> bool {
+    astar(start, neighbours, |_| 0, is_goal)
+}
+
+// The heuristic must never overestimate the cost left to a goal, or the path found may not be the cheapest
+pub fn astar<S, N, I, H, G>(start: S, mut neighbours: N, mut heuristic: H, mut is_goal: G) -> Option<Path<S>>
+    where S: Hash + Eq + Clone, N: FnMut(&S) -> I, I: IntoIterator<Item = (S, usize)>, H: FnMut(&S) -> usize, G: FnMut(&S) -> bool {
+    let mut parents = HashMap::new();
+    let mut costs = HashMap::from([(start.clone(), 0)]);
+    let mut open = BinaryHeap::new();
+    open.push(MinScored(heuristic(&start), (0, start)));
+
+    while let Some(MinScored(_, (cost, state))) = open.pop() {
+        if cost > costs[&state] {
+            continue;
+        }
+        if is_goal(&state) {
+            return Some(Path { states: walk_back(&parents, state), cost });
+        }
+        for (neighbour, step) in neighbours(&state) {
+            let neighbour_cost = cost + step;
+            if costs.get(&neighbour).is_none_or(|&known| neighbour_cost < known) {
+                costs.insert(neighbour.clone(), neighbour_cost);
+                parents.insert(neighbour.clone(), state.clone());
+                open.push(MinScored(neighbour_cost + heuristic(&neighbour), (neighbour_cost, neighbour)));
+            }
+        }
+    }
+
+    None
+}
+
+// Searches out from both ends at once, a layer at a time from whichever side has the smaller frontier.
+// Every step has to be reversible, since the same neighbours are used walking back from the goal.
+pub fn bidirectional<S, N, I>(start: S, goal: S, neighbours: N) -> Option<Path<S>>
+    where S: Hash + Eq + Clone, N: Fn(&S) -> I, I: IntoIterator<Item = S> {
+    if start == goal {
+        return Some(Path { states: vec![start], cost: 0 });
+    }
+
+    let mut forwards = Side::new(start);
+    let mut backwards = Side::new(goal);
+
+    while !forwards.frontier.is_empty() && !backwards.frontier.is_empty() {
+        let (expanding, other) = match forwards.frontier.len() <= backwards.frontier.len() {
+            true => (&mut forwards, &backwards),
+            false => (&mut backwards, &forwards),
+        };
+
+        if let Some(meeting) = expanding.expand(&neighbours, other) {
+            let mut states = walk_back(&forwards.parents, meeting.clone());
+            let mut rest = walk_back(&backwards.parents, meeting);
+            rest.reverse();
+            states.extend(rest.into_iter().skip(1));
+            let cost = states.len() - 1;
+            return Some(Path { states, cost });
+        }
+    }
+
+    None
+}
+
+struct Side<S> {
+    depths: HashMap<S, usize>,
+    parents: HashMap<S, S>,
+    frontier: Vec<S>,
+}
+
+impl<S: Hash + Eq + Clone> Side<S> {
+    fn new(from: S) -> Side<S> {
+        Side { depths: HashMap::from([(from.clone(), 0)]), parents: HashMap::new(), frontier: vec![from] }
+    }
+
+    // Moves the frontier on a layer. Of the states the other side has already seen, returns the one
+    // on the shortest path through, having looked at the whole layer first.
+    fn expand<N, I>(&mut self, neighbours: &N, other: &Side<S>) -> Option<S> where N: Fn(&S) -> I, I: IntoIterator<Item = S> {
+        let mut next = Vec::new();
+        let mut meeting: Option<(usize, S)> = None;
+
+        for state in std::mem::take(&mut self.frontier) {
+            let depth = self.depths[&state] + 1;
+            for neighbour in neighbours(&state) {
+                if self.depths.contains_key(&neighbour) {
+                    continue;
+                }
+                self.depths.insert(neighbour.clone(), depth);
+                self.parents.insert(neighbour.clone(), state.clone());
+                if let Some(&remaining) = other.depths.get(&neighbour) {
+                    if meeting.as_ref().is_none_or(|(best, _)| depth + remaining < *best) {
+                        meeting = Some((depth + remaining, neighbour.clone()));
+                    }
+                }
+                next.push(neighbour);
+            }
+        }
+
+        self.frontier = next;
+        meeting.map(|(_, state)| state)
+    }
+}
+
+#[test]
+fn pops_lowest_score_first() {
+    let mut heap = std::collections::BinaryHeap::new();
+    heap.push(MinScored(3, 'c'));
+    heap.push(MinScored(1, 'a'));
+    heap.push(MinScored(2, 'b'));
+
+    let order = std::iter::from_fn(|| heap.pop().map(|MinScored(_, c)| c)).collect::<String>();
+    assert_eq!("abc", order);
+}
+
+// A small cycle with a shortcut: 0 - 1 - 2 - 3 - 4 - 5 - 0, plus 1 - 4
+#[cfg(test)]
+fn ring(n: &u32) -> Vec<u32> {
+    let mut next = vec![(n + 1) % 6, (n + 5) % 6];
+    match n {
+        1 => next.push(4),
+        4 => next.push(1),
+        _ => (),
+    }
+    next
+}
+
+#[test]
+fn breadth_first() {
+    assert_eq!(Some(Path { states: vec![0, 1, 2], cost: 2 }), bfs(0, ring, |&n| n == 2));
+    assert_eq!(Some(Path { states: vec![0, 1, 4], cost: 2 }), bfs(0, ring, |&n| n == 4));
+    assert_eq!(Some(3), bfs(0, ring, |&n| n == 3).map(|path| path.cost));
+    assert_eq!(None, bfs(0, ring, |&n| n == 6));
+    assert_eq!(None, bfs_within(0, ring, |&n| n == 3, 2));
+    assert_eq!(Some(2), bfs_within(0, ring, |&n| n == 2, 2).map(|path| path.cost));
+
+    let reachable = reachable_within(0, ring, 1);
+    assert_eq!(3, reachable.len());
+    assert_eq!(Some(&1), reachable.get(&5));
+    assert_eq!(6, reachable_within(0, ring, 10).len());
+}
+
+#[test]
+fn weighted() {
+    // Stepping up costs the new value, so going the long way round through small numbers is cheaper
+    let steps = |&n: &u32| ring(&n).into_iter().map(|m| (m, m as usize)).collect::<Vec<(u32, usize)>>();
+    assert_eq!(Some(Path { states: vec![0, 1, 2, 3], cost: 6 }), dijkstra(0, steps, |&n| n == 3));
+    assert_eq!(Some(Path { states: vec![0, 1, 2, 3], cost: 6 }), astar(0, steps, |&n| (3 - n.min(3)) as usize, |&n| n == 3));
+    assert_eq!(None, dijkstra(0, steps, |&n| n == 7));
+}
+
+#[test]
+fn from_both_ends() {
+    let path = bidirectional(0, 3, ring).unwrap();
+    assert_eq!(3, path.cost);
+    assert_eq!((Some(&0), Some(&3)), (path.states.first(), path.states.last()));
+    assert!(path.states.windows(2).all(|step| ring(&step[0]).contains(&step[1])));
+
+    assert_eq!(Some(Path { states: vec![2], cost: 0 }), bidirectional(2, 2, ring));
+    assert_eq!(Some(Path { states: vec![2, 3], cost: 1 }), bidirectional(2, 3, ring));
+    assert_eq!(None, bidirectional(0, 6, |&n: &u32| if n < 6 { ring(&n) } else { vec![] }));
+
+    // Long chains of numbers meet in the middle
+    let line = |&n: &i32| vec![n - 1, n + 1];
+    assert_eq!(40, bidirectional(-20, 20, line).unwrap().cost);
+}
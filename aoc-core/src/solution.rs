@@ -0,0 +1,105 @@
+use std::fmt;
+use crate::params::Params;
+use crate::parse::ParseError;
+use crate::random::Rng;
+use crate::svg::Picture;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Answer {
+    Int(i64),
+    Text(String),
+    // Multi-line pictures such as a rendered screen, read by eye rather than compared as text
+    Art(String),
+}
+
+impl Answer {
+    pub fn type_name(&self) -> &'static str {
+        match *self {
+            Answer::Int(_) => "int",
+            Answer::Text(_) => "text",
+            Answer::Art(_) => "art",
+        }
+    }
+
+    // The bare answer, without the leading newline Display puts in front of art
+    pub fn value(&self) -> String {
+        match *self {
+            Answer::Art(ref s) => s.clone(),
+            _ => self.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Answer::Int(x) => write!(f, "{}", x),
+            Answer::Text(ref s) => write!(f, "{}", s),
+            Answer::Art(ref s) => write!(f, "\n{}", s),
+        }
+    }
+}
+
+macro_rules! answer_from_int {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(x: $t) -> Answer {
+                Answer::Int(x as i64)
+            }
+        })*
+    };
+}
+
+answer_from_int!(i32, i64, u32, usize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        Answer::Text(s)
+    }
+}
+
+impl<'a> From<&'a str> for Answer {
+    fn from(s: &'a str) -> Answer {
+        Answer::Text(s.to_string())
+    }
+}
+
+// Parameters such as day_13's target square come from `params`, with each day supplying its
+// own puzzle's values as defaults
+pub trait Solution {
+    fn part_one(&self, input: &str, params: &Params) -> Result<Answer, ParseError>;
+    fn part_two(&self, input: &str, params: &Params) -> Result<Answer, ParseError>;
+
+    // A random input both parts can solve with the default params. `size` is roughly how many
+    // lines or instructions it has; what exactly it counts is up to each day.
+    fn generate(&self, rng: &mut Rng, size: usize) -> String;
+
+    // A drawing of the solution for days with something spatial to show, such as a path or a screen
+    fn picture(&self, _input: &str, _params: &Params) -> Result<Option<Picture>, ParseError> {
+        Ok(None)
+    }
+
+    fn solve(&self, part: u32, input: &str, params: &Params) -> Result<Answer, ParseError> {
+        match part {
+            1 => self.part_one(input, params),
+            2 => self.part_two(input, params),
+            x => panic!("Puzzles only have two parts, asked for part {}", x)
+        }
+    }
+}
+
+#[test]
+fn answer_conversions() {
+    assert_eq!(Answer::Int(12), Answer::from(12i32));
+    assert_eq!(Answer::Int(11125026826), Answer::from(11125026826i64));
+    assert_eq!(Answer::Text("5DB3".to_string()), Answer::from("5DB3"));
+}
+
+#[test]
+fn answer_display() {
+    assert_eq!("-4", Answer::Int(-4).to_string());
+    assert_eq!("easter", Answer::Text("easter".to_string()).to_string());
+    assert_eq!("\n#.\n.#", Answer::Art("#.\n.#".to_string()).to_string());
+    assert_eq!("#.\n.#", Answer::Art("#.\n.#".to_string()).value());
+    assert_eq!("-4", Answer::Int(-4).value());
+}
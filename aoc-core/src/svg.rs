@@ -0,0 +1,112 @@
+use std::fmt::Write;
+use crate::grid::{Grid, Point};
+
+// Colours are anything SVG accepts, such as "black" or "#1f77b4"
+#[derive(Clone, Debug, PartialEq)]
+pub struct Style {
+    pub cell_size: u32,
+    pub background: String,
+    pub foreground: String,
+    pub path: String,
+}
+
+impl Default for Style {
+    fn default() -> Style {
+        Style { cell_size: 10, background: "white".to_string(), foreground: "black".to_string(), path: "crimson".to_string() }
+    }
+}
+
+// Something spatial a day can draw: a grid with its filled cells in the foreground colour, a path
+// through cells, or both. Points are (x, y) cells with y growing downwards, as on the screen.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Picture {
+    pub grid: Option<Grid<bool>>,
+    pub path: Vec<Point>,
+}
+
+impl Picture {
+    pub fn grid(grid: Grid<bool>) -> Picture {
+        Picture { grid: Some(grid), path: Vec::new() }
+    }
+
+    pub fn path(path: Vec<Point>) -> Picture {
+        Picture { grid: None, path }
+    }
+
+    pub fn route(grid: Grid<bool>, path: Vec<Point>) -> Picture {
+        Picture { grid: Some(grid), path }
+    }
+
+    // The cells covered, as the top left and bottom right corners
+    fn bounds(&self) -> (Point, Point) {
+        let mut corners = self.path.clone();
+        if let Some(ref grid) = self.grid {
+            corners.extend([(0, 0), (grid.width() as i32 - 1, grid.height() as i32 - 1)]);
+        }
+        let min = |axis: fn(&Point) -> i32| corners.iter().map(axis).min().unwrap_or(0);
+        let max = |axis: fn(&Point) -> i32| corners.iter().map(axis).max().unwrap_or(0);
+        ((min(|p| p.0), min(|p| p.1)), (max(|p| p.0), max(|p| p.1)))
+    }
+
+    // A standalone SVG document, one `cell_size` square per cell
+    pub fn to_svg(&self, style: &Style) -> String {
+        let ((left, top), (right, bottom)) = self.bounds();
+        let cell = style.cell_size as i32;
+        let (width, height) = ((right - left + 1) * cell, (bottom - top + 1) * cell);
+        let corner = |(x, y): Point| ((x - left) * cell, (y - top) * cell);
+        let centre = |point: Point| (corner(point).0 + cell / 2, corner(point).1 + cell / 2);
+
+        let mut svg = String::new();
+        writeln!(svg, r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}">"#, width, height).unwrap();
+        writeln!(svg, r#"<rect width="{}" height="{}" fill="{}"/>"#, width, height, style.background).unwrap();
+
+        if let Some(ref grid) = self.grid {
+            for point in grid.points().filter(|&point| grid[point]) {
+                let (x, y) = corner(point);
+                writeln!(svg, r#"<rect x="{}" y="{}" width="{2}" height="{2}" fill="{3}"/>"#, x, y, cell, style.foreground).unwrap();
+            }
+        }
+
+        if let (Some(&start), Some(&end)) = (self.path.first(), self.path.last()) {
+            let points = self.path.iter().map(|&point| format!("{},{}", centre(point).0, centre(point).1)).collect::<Vec<String>>();
+            writeln!(svg, r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="{}" stroke-linecap="round" stroke-linejoin="round"/>"#,
+                     points.join(" "), style.path, (cell / 3).max(1)).unwrap();
+            for point in [start, end] {
+                writeln!(svg, r#"<circle cx="{}" cy="{}" r="{}" fill="{}"/>"#, centre(point).0, centre(point).1, (cell / 2).max(1), style.path).unwrap();
+            }
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+#[test]
+fn grid_pictures() {
+    let grid = Grid::parse("#.\n.#", |c| Some(c == '#'), "a cell").unwrap();
+    let svg = Picture::grid(grid).to_svg(&Style { cell_size: 4, ..Style::default() });
+
+    assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="8" height="8" viewBox="0 0 8 8">"#));
+    assert!(svg.contains(r#"<rect width="8" height="8" fill="white"/>"#));
+    assert!(svg.contains(r#"<rect x="0" y="0" width="4" height="4" fill="black"/>"#));
+    assert!(svg.contains(r#"<rect x="4" y="4" width="4" height="4" fill="black"/>"#));
+    assert_eq!(3, svg.matches("<rect").count());
+    assert!(!svg.contains("polyline"));
+    assert!(svg.ends_with("</svg>\n"));
+}
+
+#[test]
+fn path_pictures() {
+    // The picture is cropped to the path, wherever it wanders
+    let style = Style { cell_size: 10, background: "#fff".to_string(), foreground: "grey".to_string(), path: "blue".to_string() };
+    let svg = Picture::path(vec![(0, 0), (-1, 0), (-1, 2)]).to_svg(&style);
+
+    assert!(svg.contains(r#"width="20" height="30""#));
+    assert!(svg.contains(r#"<polyline points="15,5 5,5 5,25" fill="none" stroke="blue" stroke-width="3""#));
+    assert!(svg.contains(r#"<circle cx="15" cy="5" r="5" fill="blue"/>"#));
+    assert!(svg.contains(r#"<circle cx="5" cy="25" r="5" fill="blue"/>"#));
+
+    let grid = Grid::new(3, 3, false);
+    let svg = Picture::route(grid, vec![(1, 1), (1, 4)]).to_svg(&style);
+    assert!(svg.contains(r#"width="30" height="50""#));
+}
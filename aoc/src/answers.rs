@@ -0,0 +1,47 @@
+use std::fs;
+use aoc_core::ini::Ini;
+
+pub const DEFAULT_ANSWERS: &str = "answers.ini";
+
+// Known answers, keyed by `[day_N/input_name]` sections holding `part_1` and `part_2`
+pub struct Answers {
+    ini: Ini,
+}
+
+fn section_name(day: u32, input_name: &str) -> String {
+    format!("day_{}/{}", day, input_name)
+}
+
+impl Answers {
+    pub fn load(path: &str) -> Result<Answers, String> {
+        let text = fs::read_to_string(path).map_err(|e| format!("Couldn't read {}: {}", path, e))?;
+        Answers::parse(&text).map_err(|e| format!("{}: {}", path, e))
+    }
+
+    pub fn parse(text: &str) -> Result<Answers, aoc_core::parse::ParseError> {
+        Ok(Answers { ini: Ini::parse(text)? })
+    }
+
+    pub fn expected(&self, day: u32, input_name: &str, part: u32) -> Option<&str> {
+        self.ini.get(&section_name(day, input_name), &format!("part_{}", part))
+    }
+
+    // Every input with a recorded answer for the day, in file order
+    pub fn input_names(&self, day: u32) -> Vec<String> {
+        let prefix = format!("day_{}/", day);
+        self.ini.section_names().iter()
+            .filter_map(|name| name.strip_prefix(&prefix))
+            .map(|name| name.to_string())
+            .collect()
+    }
+}
+
+#[test]
+fn expected_answers() {
+    let answers = Answers::parse("[day_1/input.txt]\npart_1 = 236\n\n[day_10/input.txt]\npart_1 = 161\n\n[day_10/marco_input.txt]\npart_2 = 7\n").unwrap();
+    assert_eq!(Some("236"), answers.expected(1, "input.txt", 1));
+    assert_eq!(None, answers.expected(1, "input.txt", 2));
+    assert_eq!(Some("7"), answers.expected(10, "marco_input.txt", 2));
+    assert_eq!(vec!("input.txt".to_string(), "marco_input.txt".to_string()), answers.input_names(10));
+    assert_eq!(vec!("input.txt".to_string()), answers.input_names(1));
+}
@@ -0,0 +1,439 @@
+use aoc_core::log::Level;
+use aoc_core::svg::Style;
+use crate::answers::DEFAULT_ANSWERS;
+use crate::report::Format;
+use crate::submit::DEFAULT_HISTORY;
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run(RunOptions),
+    Verify(VerifyOptions),
+    Bench(BenchOptions),
+    Fetch(FetchOptions),
+    Submit(SubmitOptions),
+    Compare(CompareOptions),
+    Generate(GenerateOptions),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RunOptions {
+    pub day: Option<u32>,
+    pub part: Option<u32>,
+    pub input: Option<String>,
+    pub config: Option<String>,
+    pub format: Format,
+    pub all: bool,
+    pub svg: Option<String>,
+    pub style: Style,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct VerifyOptions {
+    pub day: Option<u32>,
+    pub answers: String,
+    pub timeout: u64,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BenchOptions {
+    pub day: Option<u32>,
+    pub part: Option<u32>,
+    pub runs: u32,
+    pub timeout: u64,
+    pub baseline: Option<String>,
+    pub save: Option<String>,
+    pub threshold: u32,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct FetchOptions {
+    pub day: u32,
+    pub name: String,
+    pub cache: String,
+    pub base_url: Option<String>,
+    pub session_file: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SubmitOptions {
+    pub day: u32,
+    pub part: u32,
+    pub answer: Option<String>,
+    pub history: String,
+    pub wait: bool,
+    pub base_url: Option<String>,
+    pub session_file: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct CompareOptions {
+    pub day: u32,
+    pub inputs: Option<String>,
+    pub answers: String,
+    pub timeout: u64,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct GenerateOptions {
+    pub day: u32,
+    pub seed: u64,
+    pub size: usize,
+}
+
+fn parse_number(flag: &str, value: Option<&String>, max: u32) -> Result<u32, String> {
+    let value = value.ok_or(format!("{} needs a value", flag))?;
+    match value.parse::<u32>() {
+        Ok(x) if x >= 1 && x <= max => Ok(x),
+        _ => Err(format!("{} must be between 1 and {}, got {:?}", flag, max, value)),
+    }
+}
+
+fn parse_format(value: Option<&String>) -> Result<Format, String> {
+    match value.map(|s| s.as_str()) {
+        Some("text") => Ok(Format::Text),
+        Some("json") => Ok(Format::Json),
+        Some("jsonl") => Ok(Format::JsonLines),
+        Some(x) => Err(format!("--format must be text, json or jsonl, got {:?}", x)),
+        None => Err("--format needs a value".to_string()),
+    }
+}
+
+// Foreground, background and path colours, such as "black,white,crimson"
+fn parse_colours(value: Option<&String>, style: Style) -> Result<Style, String> {
+    let value = value.ok_or("--colours needs a value")?;
+    match value.split(',').collect::<Vec<&str>>()[..] {
+        [foreground, background, path] if !value.contains(char::is_whitespace) => {
+            Ok(Style { foreground: foreground.to_string(), background: background.to_string(), path: path.to_string(), ..style })
+        },
+        _ => Err(format!("--colours takes foreground, background and path colours like black,white,crimson, got {:?}", value)),
+    }
+}
+
+fn parse_run(args: &[String]) -> Result<Command, String> {
+    let mut options = RunOptions { day: None, part: None, input: None, config: None, format: Format::Text, all: false, svg: None, style: Style::default() };
+    let mut styled = false;
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--day" => options.day = Some(parse_number("--day", iter.next(), 25)?),
+            "--part" => options.part = Some(parse_number("--part", iter.next(), 2)?),
+            "--input" => options.input = Some(iter.next().ok_or("--input needs a value")?.to_string()),
+            "--config" => options.config = Some(iter.next().ok_or("--config needs a value")?.to_string()),
+            "--format" => options.format = parse_format(iter.next())?,
+            "--all" => options.all = true,
+            "--svg" => options.svg = Some(iter.next().ok_or("--svg needs a value")?.to_string()),
+            "--cell-size" => {
+                options.style.cell_size = parse_number("--cell-size", iter.next(), 1000)?;
+                styled = true;
+            },
+            "--colours" => {
+                options.style = parse_colours(iter.next(), options.style)?;
+                styled = true;
+            },
+            x => return Err(format!("Unexpected argument {:?}", x)),
+        }
+    }
+
+    if styled && options.svg.is_none() {
+        return Err("--cell-size and --colours only apply with --svg".to_string());
+    }
+
+    match (options.all, options.day, options.input.is_some() || options.config.is_some() || options.svg.is_some()) {
+        (true, Some(_), _) => Err("--all and --day can't be used together".to_string()),
+        (true, None, true) => Err("--input, --config and --svg can only be used with --day".to_string()),
+        (false, None, _) => Err("Either --day or --all is required".to_string()),
+        _ => Ok(Command::Run(options)),
+    }
+}
+
+fn parse_verify(args: &[String]) -> Result<Command, String> {
+    let mut options = VerifyOptions { day: None, answers: DEFAULT_ANSWERS.to_string(), timeout: 60 };
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--day" => options.day = Some(parse_number("--day", iter.next(), 25)?),
+            "--answers" => options.answers = iter.next().ok_or("--answers needs a value")?.to_string(),
+            "--timeout" => options.timeout = parse_number("--timeout", iter.next(), u32::MAX)? as u64,
+            x => return Err(format!("Unexpected argument {:?}", x)),
+        }
+    }
+
+    Ok(Command::Verify(options))
+}
+
+fn parse_bench(args: &[String]) -> Result<Command, String> {
+    let mut options = BenchOptions { day: None, part: None, runs: 5, timeout: 60, baseline: None, save: None, threshold: 10 };
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--day" => options.day = Some(parse_number("--day", iter.next(), 25)?),
+            "--part" => options.part = Some(parse_number("--part", iter.next(), 2)?),
+            "--runs" => options.runs = parse_number("--runs", iter.next(), 10000)?,
+            "--timeout" => options.timeout = parse_number("--timeout", iter.next(), u32::MAX)? as u64,
+            "--baseline" => options.baseline = Some(iter.next().ok_or("--baseline needs a value")?.to_string()),
+            "--save" => options.save = Some(iter.next().ok_or("--save needs a value")?.to_string()),
+            "--threshold" => options.threshold = parse_number("--threshold", iter.next(), 1000)?,
+            x => return Err(format!("Unexpected argument {:?}", x)),
+        }
+    }
+
+    Ok(Command::Bench(options))
+}
+
+fn parse_fetch(args: &[String]) -> Result<Command, String> {
+    let mut day = None;
+    let mut options = FetchOptions { day: 0, name: "input.txt".to_string(), cache: ".".to_string(), base_url: None, session_file: None };
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--day" => day = Some(parse_number("--day", iter.next(), 25)?),
+            "--name" => options.name = iter.next().ok_or("--name needs a value")?.to_string(),
+            "--cache" => options.cache = iter.next().ok_or("--cache needs a value")?.to_string(),
+            "--base-url" => options.base_url = Some(iter.next().ok_or("--base-url needs a value")?.to_string()),
+            "--session-file" => options.session_file = Some(iter.next().ok_or("--session-file needs a value")?.to_string()),
+            x => return Err(format!("Unexpected argument {:?}", x)),
+        }
+    }
+
+    options.day = day.ok_or("--day is required")?;
+    Ok(Command::Fetch(options))
+}
+
+fn parse_submit(args: &[String]) -> Result<Command, String> {
+    let (mut day, mut part) = (None, None);
+    let mut options = SubmitOptions { day: 0, part: 0, answer: None, history: DEFAULT_HISTORY.to_string(), wait: false, base_url: None, session_file: None };
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--day" => day = Some(parse_number("--day", iter.next(), 25)?),
+            "--part" => part = Some(parse_number("--part", iter.next(), 2)?),
+            "--answer" => options.answer = Some(iter.next().ok_or("--answer needs a value")?.to_string()),
+            "--history" => options.history = iter.next().ok_or("--history needs a value")?.to_string(),
+            "--wait" => options.wait = true,
+            "--base-url" => options.base_url = Some(iter.next().ok_or("--base-url needs a value")?.to_string()),
+            "--session-file" => options.session_file = Some(iter.next().ok_or("--session-file needs a value")?.to_string()),
+            x => return Err(format!("Unexpected argument {:?}", x)),
+        }
+    }
+
+    options.day = day.ok_or("--day is required")?;
+    options.part = part.ok_or("--part is required")?;
+    Ok(Command::Submit(options))
+}
+
+fn parse_compare(args: &[String]) -> Result<Command, String> {
+    let mut day = None;
+    let mut options = CompareOptions { day: 0, inputs: None, answers: DEFAULT_ANSWERS.to_string(), timeout: 60 };
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--day" => day = Some(parse_number("--day", iter.next(), 25)?),
+            "--inputs" => options.inputs = Some(iter.next().ok_or("--inputs needs a value")?.to_string()),
+            "--answers" => options.answers = iter.next().ok_or("--answers needs a value")?.to_string(),
+            "--timeout" => options.timeout = parse_number("--timeout", iter.next(), u32::MAX)? as u64,
+            x => return Err(format!("Unexpected argument {:?}", x)),
+        }
+    }
+
+    options.day = day.ok_or("--day is required")?;
+    Ok(Command::Compare(options))
+}
+
+fn parse_generate(args: &[String]) -> Result<Command, String> {
+    let mut day = None;
+    let mut options = GenerateOptions { day: 0, seed: 1, size: 20 };
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--day" => day = Some(parse_number("--day", iter.next(), 25)?),
+            "--seed" => options.seed = parse_number("--seed", iter.next(), u32::MAX)? as u64,
+            "--size" => options.size = parse_number("--size", iter.next(), 100_000)? as usize,
+            x => return Err(format!("Unexpected argument {:?}", x)),
+        }
+    }
+
+    options.day = day.ok_or("--day is required")?;
+    Ok(Command::Generate(options))
+}
+
+// Options that stand alone. Every other option takes the argument after it as its value.
+const SWITCHES: [&str; 3] = ["--all", "--wait", "--verbose"];
+
+// Takes the -v, -vv and --verbose flags out of the arguments wherever an option could go, leaving
+// option values such as `--answer -v` alone
+pub fn verbosity(args: &[String]) -> (Level, Vec<String>) {
+    let mut count = 0;
+    let mut rest = Vec::new();
+    let mut value_next = false;
+
+    for arg in args {
+        match arg.as_str() {
+            _ if value_next => {
+                rest.push(arg.clone());
+                value_next = false;
+            },
+            "--verbose" => count += 1,
+            flag if flag.len() > 1 && flag.starts_with('-') && flag[1..].chars().all(|c| c == 'v') => count += flag.len() - 1,
+            option => {
+                value_next = option.starts_with("--") && !SWITCHES.contains(&option);
+                rest.push(arg.clone());
+            },
+        }
+    }
+
+    (Level::from_verbosity(count), rest)
+}
+
+pub fn parse(args: &[String]) -> Result<Command, String> {
+    match args.first().map(|s| s.as_str()) {
+        Some("run") => parse_run(&args[1..]),
+        Some("verify") => parse_verify(&args[1..]),
+        Some("bench") => parse_bench(&args[1..]),
+        Some("fetch") => parse_fetch(&args[1..]),
+        Some("submit") => parse_submit(&args[1..]),
+        Some("compare") => parse_compare(&args[1..]),
+        Some("generate") => parse_generate(&args[1..]),
+        Some(x) => Err(format!("Unknown command {:?}", x)),
+        None => Err("No command given".to_string()),
+    }
+}
+
+#[cfg(test)]
+fn to_args(line: &str) -> Vec<String> {
+    line.split_whitespace().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn run_single_part() {
+    let expected = RunOptions { day: Some(13), part: Some(2), input: Some("maze.txt".to_string()), config: None, format: Format::Text, all: false, svg: None, style: Style::default() };
+    assert_eq!(Ok(Command::Run(expected)), parse(&to_args("run --day 13 --part 2 --input maze.txt")));
+
+    let expected = RunOptions { day: Some(13), part: None, input: None, config: Some("maze.ini".to_string()), format: Format::Text, all: false, svg: None, style: Style::default() };
+    assert_eq!(Ok(Command::Run(expected)), parse(&to_args("run --day 13 --config maze.ini")));
+}
+
+#[test]
+fn run_all() {
+    let expected = RunOptions { day: None, part: None, input: None, config: None, format: Format::Text, all: true, svg: None, style: Style::default() };
+    assert_eq!(Ok(Command::Run(expected)), parse(&to_args("run --all")));
+
+    let expected = RunOptions { day: None, part: None, input: None, config: None, format: Format::JsonLines, all: true, svg: None, style: Style::default() };
+    assert_eq!(Ok(Command::Run(expected)), parse(&to_args("run --all --format jsonl")));
+}
+
+#[test]
+fn run_with_svg() {
+    let style = Style { cell_size: 4, foreground: "navy".to_string(), background: "#eee".to_string(), path: "gold".to_string() };
+    let expected = RunOptions { day: Some(13), part: None, input: None, config: None, format: Format::Text, all: false, svg: Some("maze.svg".to_string()), style };
+    assert_eq!(Ok(Command::Run(expected)), parse(&to_args("run --day 13 --svg maze.svg --cell-size 4 --colours navy,#eee,gold")));
+
+    assert!(parse(&to_args("run --all --svg all.svg")).is_err());
+    assert!(parse(&to_args("run --day 1 --cell-size 4")).is_err());
+    assert!(parse(&to_args("run --day 1 --svg out.svg --colours black,white")).is_err());
+}
+
+#[test]
+fn verify_options() {
+    let expected = VerifyOptions { day: None, answers: DEFAULT_ANSWERS.to_string(), timeout: 60 };
+    assert_eq!(Ok(Command::Verify(expected)), parse(&to_args("verify")));
+
+    let expected = VerifyOptions { day: Some(9), answers: "mine.ini".to_string(), timeout: 5 };
+    assert_eq!(Ok(Command::Verify(expected)), parse(&to_args("verify --day 9 --answers mine.ini --timeout 5")));
+
+    assert!(parse(&to_args("verify --timeout 0")).is_err());
+    assert!(parse(&to_args("verify --part 1")).is_err());
+}
+
+#[test]
+fn bench_options() {
+    let expected = BenchOptions { day: None, part: None, runs: 5, timeout: 60, baseline: None, save: None, threshold: 10 };
+    assert_eq!(Ok(Command::Bench(expected)), parse(&to_args("bench")));
+
+    let expected = BenchOptions { day: Some(5), part: Some(1), runs: 3, timeout: 60, baseline: Some("before.json".to_string()), save: Some("after.json".to_string()), threshold: 25 };
+    assert_eq!(Ok(Command::Bench(expected)), parse(&to_args("bench --day 5 --part 1 --runs 3 --baseline before.json --save after.json --threshold 25")));
+
+    assert!(parse(&to_args("bench --runs 0")).is_err());
+    assert!(parse(&to_args("bench --baseline")).is_err());
+}
+
+#[test]
+fn fetch_options() {
+    let expected = FetchOptions { day: 10, name: "input.txt".to_string(), cache: ".".to_string(), base_url: None, session_file: None };
+    assert_eq!(Ok(Command::Fetch(expected)), parse(&to_args("fetch --day 10")));
+
+    let expected = FetchOptions {
+        day: 10,
+        name: "marco_input.txt".to_string(),
+        cache: "inputs".to_string(),
+        base_url: Some("http://localhost:8000".to_string()),
+        session_file: Some("marco.session".to_string()),
+    };
+    assert_eq!(Ok(Command::Fetch(expected)), parse(&to_args("fetch --day 10 --name marco_input.txt --cache inputs --base-url http://localhost:8000 --session-file marco.session")));
+
+    assert!(parse(&to_args("fetch")).is_err());
+    assert!(parse(&to_args("fetch --day 10 --name")).is_err());
+}
+
+#[test]
+fn submit_options() {
+    let expected = SubmitOptions { day: 3, part: 1, answer: None, history: DEFAULT_HISTORY.to_string(), wait: false, base_url: None, session_file: None };
+    assert_eq!(Ok(Command::Submit(expected)), parse(&to_args("submit --day 3 --part 1")));
+
+    let expected = SubmitOptions { day: 8, part: 2, answer: Some("AFLUPAJS".to_string()), history: "mine.json".to_string(), wait: true, base_url: None, session_file: None };
+    assert_eq!(Ok(Command::Submit(expected)), parse(&to_args("submit --day 8 --part 2 --answer AFLUPAJS --history mine.json --wait")));
+
+    assert!(parse(&to_args("submit --day 3")).is_err());
+    assert!(parse(&to_args("submit --part 1")).is_err());
+}
+
+#[test]
+fn compare_options() {
+    let expected = CompareOptions { day: 10, inputs: None, answers: DEFAULT_ANSWERS.to_string(), timeout: 60 };
+    assert_eq!(Ok(Command::Compare(expected)), parse(&to_args("compare --day 10")));
+
+    let expected = CompareOptions { day: 10, inputs: Some("team/*.txt".to_string()), answers: "team.ini".to_string(), timeout: 5 };
+    assert_eq!(Ok(Command::Compare(expected)), parse(&to_args("compare --day 10 --inputs team/*.txt --answers team.ini --timeout 5")));
+
+    assert!(parse(&to_args("compare --inputs team")).is_err());
+}
+
+#[test]
+fn generate_options() {
+    assert_eq!(Ok(Command::Generate(GenerateOptions { day: 21, seed: 1, size: 20 })), parse(&to_args("generate --day 21")));
+    assert_eq!(Ok(Command::Generate(GenerateOptions { day: 9, seed: 42, size: 500 })), parse(&to_args("generate --day 9 --seed 42 --size 500")));
+    assert!(parse(&to_args("generate --seed 42")).is_err());
+}
+
+#[test]
+fn run_rejects_bad_arguments() {
+    assert!(parse(&to_args("run")).is_err());
+    assert!(parse(&to_args("run --day 26")).is_err());
+    assert!(parse(&to_args("run --day 1 --part 3")).is_err());
+    assert!(parse(&to_args("run --day")).is_err());
+    assert!(parse(&to_args("run --all --day 4")).is_err());
+    assert!(parse(&to_args("run --all --input foo.txt")).is_err());
+    assert!(parse(&to_args("run --all --config foo.ini")).is_err());
+    assert!(parse(&to_args("run --all --format xml")).is_err());
+    assert!(parse(&to_args("walk --day 1")).is_err());
+}
+
+#[test]
+fn verbosity_flags() {
+    assert_eq!((Level::Quiet, to_args("run --all")), verbosity(&to_args("run --all")));
+    assert_eq!((Level::Debug, to_args("run --all")), verbosity(&to_args("-v run --all")));
+    assert_eq!((Level::Trace, to_args("run --day 5")), verbosity(&to_args("run -vv --day 5")));
+    assert_eq!((Level::Trace, to_args("verify")), verbosity(&to_args("verify -v --verbose")));
+    assert_eq!((Level::Quiet, to_args("run --input -")), verbosity(&to_args("run --input -")));
+
+    // A value that looks like a flag still belongs to its option
+    assert_eq!((Level::Quiet, to_args("submit --day 1 --part 2 --answer -vv")), verbosity(&to_args("submit --day 1 --part 2 --answer -vv")));
+    assert_eq!((Level::Debug, to_args("run --day 1 --input -v")), verbosity(&to_args("run --day 1 -v --input -v")));
+    assert_eq!((Level::Debug, to_args("submit --wait --day 1")), verbosity(&to_args("submit --wait -v --day 1")));
+}
@@ -0,0 +1,194 @@
+use std::fs;
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use aoc_core::input::InputSource;
+use aoc_core::params::Params;
+use crate::args::BenchOptions;
+use crate::days;
+use crate::timing::{format_bytes, format_duration, timed_solve};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Measurement {
+    pub day: u32,
+    pub part: u32,
+    pub runs: usize,
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub max_ns: u64,
+    pub peak_bytes: usize,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Baseline {
+    pub measurements: Vec<Measurement>,
+}
+
+impl Baseline {
+    fn load(path: &str) -> Result<Baseline, String> {
+        let text = fs::read_to_string(path).map_err(|e| format!("Couldn't read {}: {}", path, e))?;
+        serde_json::from_str(&text).map_err(|e| format!("{}: {}", path, e))
+    }
+
+    fn save(&self, path: &str) -> Result<(), String> {
+        let text = serde_json::to_string_pretty(self).unwrap();
+        fs::write(path, text + "\n").map_err(|e| format!("Couldn't write {}: {}", path, e))
+    }
+
+    fn find(&self, day: u32, part: u32) -> Option<&Measurement> {
+        self.measurements.iter().find(|m| m.day == day && m.part == part)
+    }
+}
+
+pub fn summarise(day: u32, part: u32, elapsed: &[Duration], peak_bytes: &[usize]) -> Measurement {
+    let mut nanos = elapsed.iter().map(|d| d.as_nanos() as u64).collect::<Vec<u64>>();
+    nanos.sort();
+
+    let middle = nanos.len() / 2;
+    let median_ns = match nanos.len() % 2 {
+        0 => (nanos[middle - 1] + nanos[middle]) / 2,
+        _ => nanos[middle],
+    };
+
+    Measurement {
+        day,
+        part,
+        runs: nanos.len(),
+        min_ns: nanos[0],
+        median_ns,
+        max_ns: nanos[nanos.len() - 1],
+        peak_bytes: peak_bytes.iter().copied().max().unwrap_or(0),
+    }
+}
+
+fn percent_change(before: u64, after: u64) -> f64 {
+    match before {
+        0 => 0.0,
+        _ => (after as f64 - before as f64) * 100.0 / before as f64,
+    }
+}
+
+// Describes how a measurement moved against the baseline, and whether it got worse by more than
+// `threshold` percent in time or memory
+pub fn compare(current: &Measurement, baseline: &Measurement, threshold: f64) -> (String, bool) {
+    let time_change = percent_change(baseline.median_ns, current.median_ns);
+    let memory_change = percent_change(baseline.peak_bytes as u64, current.peak_bytes as u64);
+    let regressed = time_change > threshold || memory_change > threshold;
+
+    let description = format!("median {:+.1}%, peak {:+.1}%{}", time_change, memory_change, if regressed { "  REGRESSION" } else { "" });
+    (description, regressed)
+}
+
+fn describe(m: &Measurement) -> String {
+    format!("Day {:>2} part {}  {} runs  min {:>9}  median {:>9}  max {:>9}  peak {:>8}",
+            m.day, m.part, m.runs,
+            format_duration(Duration::from_nanos(m.min_ns)),
+            format_duration(Duration::from_nanos(m.median_ns)),
+            format_duration(Duration::from_nanos(m.max_ns)),
+            format_bytes(m.peak_bytes))
+}
+
+fn bench_part(day: u32, part: u32, input: &str, params: &Params, options: &BenchOptions) -> Result<Measurement, String> {
+    let timeout = Duration::from_secs(options.timeout);
+    let mut elapsed = Vec::new();
+    let mut peak_bytes = Vec::new();
+
+    for _ in 0..options.runs {
+        let run = timed_solve(day, part, input, params, timeout).ok_or(format!("timed out after {}", format_duration(timeout)))?;
+        if !run.reliable {
+            return Err("not measured, a solver that timed out earlier is still running".to_string());
+        }
+        run.result?;
+        elapsed.push(run.elapsed);
+        peak_bytes.push(run.peak_bytes);
+    }
+
+    Ok(summarise(day, part, &elapsed, &peak_bytes))
+}
+
+// Returns whether every part ran without errors or regressions
+pub fn bench(options: &BenchOptions) -> Result<bool, String> {
+    let baseline = match options.baseline {
+        Some(ref path) => Some(Baseline::load(path)?),
+        None => None,
+    };
+
+    let days = match options.day {
+        Some(day) if days::solution(day).is_none() => return Err(format!("Day {} has no solution implemented", day)),
+        Some(day) => vec!(day),
+        None => days::solved_days(),
+    };
+    let parts = match options.part {
+        Some(part) => vec!(part),
+        None => vec!(1, 2),
+    };
+
+    let mut measurements = Vec::new();
+    let mut all_ok = true;
+    for day in days {
+        let source = InputSource::File(days::input_path(day, "input.txt").into());
+        let input = source.read().map_err(|e| format!("Couldn't read {}: {}", source, e))?;
+        let params = Params::for_input(&source)?;
+
+        for &part in &parts {
+            let measurement = match bench_part(day, part, &input, &params, options) {
+                Ok(measurement) => measurement,
+                Err(message) => {
+                    println!("Day {:>2} part {}  ERROR {}", day, part, message);
+                    all_ok = false;
+                    continue;
+                }
+            };
+
+            let comparison = match baseline.as_ref().and_then(|b| b.find(day, part)) {
+                Some(before) => {
+                    let (description, regressed) = compare(&measurement, before, options.threshold as f64);
+                    all_ok &= !regressed;
+                    format!("  {}", description)
+                },
+                None if baseline.is_some() => "  not in baseline".to_string(),
+                None => String::new(),
+            };
+
+            println!("{}{}", describe(&measurement), comparison);
+            measurements.push(measurement);
+        }
+    }
+
+    if let Some(ref path) = options.save {
+        Baseline { measurements }.save(path)?;
+        println!("\nSaved baseline to {}", path);
+    }
+
+    Ok(all_ok)
+}
+
+#[test]
+fn summaries() {
+    let elapsed = [30, 10, 20, 50].iter().map(|&ms| Duration::from_millis(ms)).collect::<Vec<Duration>>();
+    let m = summarise(5, 1, &elapsed, &[100, 300, 200, 100]);
+    assert_eq!(Measurement { day: 5, part: 1, runs: 4, min_ns: 10_000_000, median_ns: 25_000_000, max_ns: 50_000_000, peak_bytes: 300 }, m);
+
+    let m = summarise(5, 2, &elapsed[..3], &[]);
+    assert_eq!((20_000_000, 0), (m.median_ns, m.peak_bytes));
+}
+
+#[test]
+fn comparisons() {
+    let before = Measurement { day: 9, part: 2, runs: 5, min_ns: 90, median_ns: 100, max_ns: 120, peak_bytes: 1000 };
+    let faster = Measurement { median_ns: 80, ..before.clone() };
+    let slower = Measurement { median_ns: 125, ..before.clone() };
+    let hungrier = Measurement { peak_bytes: 2000, ..before.clone() };
+
+    assert_eq!(("median -20.0%, peak +0.0%".to_string(), false), compare(&faster, &before, 10.0));
+    assert_eq!(("median +25.0%, peak +0.0%  REGRESSION".to_string(), true), compare(&slower, &before, 10.0));
+    assert_eq!(("median +25.0%, peak +0.0%".to_string(), false), compare(&slower, &before, 30.0));
+    assert_eq!(("median +0.0%, peak +100.0%  REGRESSION".to_string(), true), compare(&hungrier, &before, 10.0));
+}
+
+#[test]
+fn baseline_json() {
+    let baseline = Baseline { measurements: vec!(Measurement { day: 1, part: 2, runs: 3, min_ns: 1, median_ns: 2, max_ns: 3, peak_bytes: 4 }) };
+    let json = serde_json::to_string(&baseline).unwrap();
+    assert_eq!(r#"{"measurements":[{"day":1,"part":2,"runs":3,"min_ns":1,"median_ns":2,"max_ns":3,"peak_bytes":4}]}"#, json);
+    assert_eq!(baseline, serde_json::from_str(&json).unwrap());
+}
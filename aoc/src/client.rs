@@ -0,0 +1,132 @@
+use std::env;
+use std::fs;
+use std::time::Duration;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const YEAR: u32 = 2016;
+
+// Talks to the puzzle site, or anything serving the same paths, as the logged-in user
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+// `--base-url`, then AOC_BASE_URL, then the real site
+pub fn base_url(arg: Option<&str>) -> String {
+    match arg {
+        Some(url) => url.to_string(),
+        None => env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.to_string()),
+    }
+}
+
+// The session cookie comes from `--session-file`, then AOC_SESSION
+pub fn session_token(session_file: Option<&str>) -> Result<String, String> {
+    let token = match session_file {
+        Some(path) => fs::read_to_string(path).map_err(|e| format!("Couldn't read {}: {}", path, e))?,
+        None => env::var("AOC_SESSION").map_err(|_| "No session token: pass --session-file or set AOC_SESSION".to_string())?,
+    };
+
+    match token.trim() {
+        "" => Err("The session token is empty".to_string()),
+        token => Ok(token.to_string()),
+    }
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Client {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            agent: ureq::AgentBuilder::new().timeout(Duration::from_secs(30)).build(),
+        }
+    }
+
+    pub fn day_url(&self, day: u32, rest: &str) -> String {
+        format!("{}/{}/day/{}{}", self.base_url, YEAR, day, rest)
+    }
+
+    fn finish(url: &str, response: Result<ureq::Response, ureq::Error>) -> Result<String, String> {
+        match response {
+            Ok(response) => response.into_string().map_err(|e| format!("Couldn't read the response from {}: {}", url, e)),
+            Err(ureq::Error::Status(code, response)) => {
+                let body = response.into_string().unwrap_or_default();
+                Err(format!("{} returned {}: {}", url, code, body.trim()))
+            },
+            Err(e) => Err(format!("Couldn't reach {}: {}", url, e)),
+        }
+    }
+
+    pub fn get(&self, url: &str) -> Result<String, String> {
+        let response = self.agent.get(url).set("Cookie", &format!("session={}", self.session)).call();
+        Client::finish(url, response)
+    }
+
+    pub fn post(&self, url: &str, form: &[(&str, String)]) -> Result<String, String> {
+        let form = form.iter().map(|(key, value)| (*key, value.as_str())).collect::<Vec<(&str, &str)>>();
+        let response = self.agent.post(url).set("Cookie", &format!("session={}", self.session)).send_form(&form);
+        Client::finish(url, response)
+    }
+}
+
+// A single-threaded HTTP server on a free local port that answers every request with `respond`
+// and keeps each raw request for the test to inspect
+#[cfg(test)]
+pub fn stand_in_server<F>(respond: F) -> (String, std::sync::Arc<std::sync::Mutex<Vec<String>>>)
+    where F: Fn(&str) -> (u16, String) + Send + 'static {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let seen = requests.clone();
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut request = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line).unwrap() == 0 || line == "\r\n" {
+                    break;
+                }
+                if let Some(length) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = length.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            request.push_str("\r\n");
+            request.push_str(&String::from_utf8(body).unwrap());
+
+            let (status, body) = respond(&request);
+            seen.lock().unwrap().push(request);
+            write!(stream, "HTTP/1.1 {} Stand-in\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", status, body.len(), body).unwrap();
+        }
+    });
+
+    (base_url, requests)
+}
+
+#[test]
+fn get_with_session() {
+    let (base_url, requests) = stand_in_server(|request| match request.starts_with("GET /2016/day/3/input ") {
+        true => (200, "101 301 501\n".to_string()),
+        false => (404, "Not Found".to_string()),
+    });
+    let client = Client::new(&(base_url.clone() + "/"), "abc123");
+
+    assert_eq!(format!("{}/2016/day/3/input", base_url), client.day_url(3, "/input"));
+    assert_eq!(Ok("101 301 501\n".to_string()), client.get(&client.day_url(3, "/input")));
+    assert!(requests.lock().unwrap()[0].contains("Cookie: session=abc123\r\n"));
+
+    let url = client.day_url(26, "/input");
+    assert_eq!(Err(format!("{} returned 404: Not Found", url)), client.get(&url));
+}
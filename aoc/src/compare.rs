@@ -0,0 +1,117 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use crate::answers::Answers;
+use crate::args::CompareOptions;
+use crate::days;
+use crate::timing::format_duration;
+use crate::verify::{check_input, Check, Status};
+
+// A directory means every `.txt` file in it; anything else is treated as a glob
+pub fn input_paths(pattern: &str) -> Result<Vec<PathBuf>, String> {
+    let mut paths = match Path::new(pattern).is_dir() {
+        true => fs::read_dir(pattern).map_err(|e| format!("Couldn't read {}: {}", pattern, e))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "txt"))
+            .collect::<Vec<PathBuf>>(),
+        false => glob::glob(pattern).map_err(|e| format!("Bad pattern {:?}: {}", pattern, e))?
+            .filter_map(|path| path.ok())
+            .filter(|path| path.is_file())
+            .collect(),
+    };
+
+    paths.sort();
+    match paths.is_empty() {
+        true => Err(format!("No inputs found in {}", pattern)),
+        false => Ok(paths),
+    }
+}
+
+fn short(answer: &str) -> String {
+    match answer.contains('\n') {
+        true => format!("[{} line drawing]", answer.lines().count()),
+        false => answer.to_string(),
+    }
+}
+
+// A pass only keeps its status, so its answer is the recorded one it matched
+fn cell(check: &Check, expected: Option<&str>) -> String {
+    match check.status {
+        Status::Pass => format!("{} ok", short(expected.unwrap_or_default())),
+        Status::Missing { ref actual } => short(actual),
+        Status::Fail { ref expected, ref actual } => format!("{} MISMATCH, expected {}", short(actual), short(expected)),
+        Status::Error(ref message) => format!("ERROR {}", message),
+        Status::Timeout => "TIMEOUT".to_string(),
+    }
+}
+
+// Lines up columns under a header, padding every cell but the last to the widest in its column
+pub fn render_table(rows: &[Vec<String>]) -> String {
+    let columns = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let widths = (0..columns).map(|i| rows.iter().filter_map(|row| row.get(i)).map(|cell| cell.chars().count()).max().unwrap_or(0)).collect::<Vec<usize>>();
+
+    rows.iter().map(|row| {
+        row.iter().enumerate()
+            .map(|(i, cell)| format!("{:<width$}", cell, width = widths[i]))
+            .collect::<Vec<String>>().join("  ").trim_end().to_string()
+    }).collect::<Vec<String>>().join("\n")
+}
+
+// Returns whether every input agreed with the registry wherever it has an answer
+pub fn compare(options: &CompareOptions) -> Result<bool, String> {
+    if days::solution(options.day).is_none() {
+        return Err(format!("Day {} has no solution implemented", options.day));
+    }
+
+    let answers = Answers::load(&options.answers)?;
+    let pattern = options.inputs.clone().unwrap_or_else(|| days::input_path(options.day, ""));
+    let timeout = Duration::from_secs(options.timeout);
+
+    let mut rows = vec!(vec!("Input".to_string(), "Part 1".to_string(), "Part 2".to_string(), "Time".to_string()));
+    let mut all_ok = true;
+    for path in input_paths(&pattern)? {
+        let input_name = path.file_name().unwrap().to_string_lossy().to_string();
+        let checks = check_input(options.day, &path, &input_name, &answers, timeout);
+        all_ok &= checks.iter().all(|check| check.is_ok());
+
+        let elapsed = checks.iter().map(|check| check.elapsed).sum::<Duration>();
+        let time = match checks.iter().all(|check| check.reliable) {
+            true => format_duration(elapsed),
+            false => format!("{} (unreliable, a timed-out solver was still running)", format_duration(elapsed)),
+        };
+        let cells = checks.iter().map(|check| cell(check, answers.expected(options.day, &input_name, check.part))).collect::<Vec<String>>();
+        rows.push(vec!(input_name, cells[0].clone(), cells[1].clone(), time));
+    }
+
+    println!("{}", render_table(&rows));
+    Ok(all_ok)
+}
+
+#[test]
+fn finds_inputs() {
+    let names = |paths: Vec<PathBuf>| paths.iter().map(|path| path.file_name().unwrap().to_string_lossy().to_string()).collect::<Vec<String>>();
+
+    assert_eq!(vec!("input.txt", "marco_input.txt"), names(input_paths("../day_10/input").unwrap()));
+    assert_eq!(vec!("marco_input.txt"), names(input_paths("../day_10/input/m*.txt").unwrap()));
+    assert!(input_paths("../day_10/input/*.csv").is_err());
+}
+
+#[test]
+fn tables() {
+    let rows = vec!(
+        vec!("Input".to_string(), "Part 1".to_string(), "Time".to_string()),
+        vec!("marco_input.txt".to_string(), "73 MISMATCH, expected 72".to_string(), "1.20ms".to_string()),
+    );
+    assert_eq!("Input            Part 1                    Time\nmarco_input.txt  73 MISMATCH, expected 72  1.20ms", render_table(&rows));
+}
+
+#[test]
+fn cells() {
+    let check = |status| Check { day: 10, part: 1, input_name: "input.txt".to_string(), status, elapsed: Duration::from_secs(0), reliable: true };
+
+    assert_eq!("161 ok", cell(&check(Status::Pass), Some("161")));
+    assert_eq!("[2 line drawing] ok", cell(&check(Status::Pass), Some("#.\n.#")));
+    assert_eq!("73", cell(&check(Status::Missing { actual: "73".to_string() }), None));
+    assert_eq!("73 MISMATCH, expected 72", cell(&check(Status::Fail { expected: "72".to_string(), actual: "73".to_string() }), Some("72")));
+    assert_eq!("TIMEOUT", cell(&check(Status::Timeout), None));
+}
@@ -0,0 +1,33 @@
+use aoc_core::solution::Solution;
+
+pub fn solution(day: u32) -> Option<&'static dyn Solution> {
+    let solution: &'static dyn Solution = match day {
+        1 => &day_1::Day1,
+        2 => &day_2::Day2,
+        3 => &day_3::Day3,
+        4 => &day_4::Day4,
+        5 => &day_5::Day5,
+        6 => &day_6::Day6,
+        7 => &day_7::Day7,
+        8 => &day_8::Day8,
+        9 => &day_9::Day9,
+        10 => &day_10::Day10,
+        11 => &day_11::Day11,
+        12 => &day_12::Day12,
+        13 => &day_13::Day13,
+        14 => &day_14::Day14,
+        15 => &day_15::Day15,
+        21 => &day_21::Day21,
+        _ => return None,
+    };
+
+    Some(solution)
+}
+
+pub fn input_path(day: u32, input_name: &str) -> String {
+    format!("day_{}/input/{}", day, input_name)
+}
+
+pub fn solved_days() -> Vec<u32> {
+    (1..26).filter(|&day| solution(day).is_some()).collect()
+}
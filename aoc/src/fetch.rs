@@ -0,0 +1,71 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::args::FetchOptions;
+use crate::client::{base_url, session_token, Client};
+use crate::days;
+
+#[derive(Debug, PartialEq)]
+pub enum Fetched {
+    Cached,
+    Downloaded(usize),
+}
+
+// Inputs never change once published, so anything already on disk is kept as it is
+pub fn fetch_input<F>(day: u32, path: &Path, client: F) -> Result<Fetched, String>
+    where F: FnOnce() -> Result<Client, String> {
+    if path.is_file() {
+        return Ok(Fetched::Cached);
+    }
+
+    let client = client()?;
+    let input = client.get(&client.day_url(day, "/input"))?;
+    if input.trim().is_empty() {
+        return Err(format!("The input for day {} came back empty", day));
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Couldn't create {}: {}", parent.display(), e))?;
+    }
+    fs::write(path, &input).map_err(|e| format!("Couldn't write {}: {}", path.display(), e))?;
+    Ok(Fetched::Downloaded(input.len()))
+}
+
+pub fn fetch(options: &FetchOptions) -> Result<(), String> {
+    let path = PathBuf::from(&options.cache).join(days::input_path(options.day, &options.name));
+    let client = || Ok(Client::new(&base_url(options.base_url.as_deref()), &session_token(options.session_file.as_deref())?));
+
+    match fetch_input(options.day, &path, client)? {
+        Fetched::Cached => println!("Day {} input already cached at {}", options.day, path.display()),
+        Fetched::Downloaded(bytes) => println!("Day {} input saved to {} ({} bytes)", options.day, path.display(), bytes),
+    }
+    Ok(())
+}
+
+#[test]
+fn fetches_once() {
+    use crate::client::stand_in_server;
+
+    let (base_url, requests) = stand_in_server(|_| (200, "rect 3x2\n".to_string()));
+    let path = std::env::temp_dir().join(format!("aoc-fetch-{}", std::process::id())).join(days::input_path(8, "input.txt"));
+    let client = || Ok(Client::new(&base_url, "abc123"));
+
+    assert_eq!(Ok(Fetched::Downloaded(9)), fetch_input(8, &path, client));
+    assert_eq!(Ok(Fetched::Cached), fetch_input(8, &path, client));
+    assert_eq!(Ok(Fetched::Cached), fetch_input(8, &path, || Err("no session".to_string())));
+    assert_eq!("rect 3x2\n", fs::read_to_string(&path).unwrap());
+    assert_eq!(1, requests.lock().unwrap().len());
+    assert!(requests.lock().unwrap()[0].starts_with("GET /2016/day/8/input "));
+
+    fs::remove_dir_all(path.ancestors().nth(3).unwrap()).unwrap();
+}
+
+#[test]
+fn failed_fetches_leave_no_cache() {
+    use crate::client::stand_in_server;
+
+    let (base_url, _) = stand_in_server(|_| (400, "Puzzle inputs differ by user.  Please log in to get your puzzle input.".to_string()));
+    let path = std::env::temp_dir().join(format!("aoc-fetch-fail-{}", std::process::id())).join("input.txt");
+
+    assert!(fetch_input(8, &path, || Ok(Client::new(&base_url, "expired"))).unwrap_err().contains("returned 400: Puzzle inputs differ"));
+    assert!(!path.exists());
+}
@@ -0,0 +1,149 @@
+extern crate aoc_core;
+extern crate day_1;
+extern crate day_2;
+extern crate day_3;
+extern crate day_4;
+extern crate day_5;
+extern crate day_6;
+extern crate day_7;
+extern crate day_8;
+extern crate day_9;
+extern crate day_10;
+extern crate day_11;
+extern crate day_12;
+extern crate day_13;
+extern crate day_14;
+extern crate day_15;
+extern crate day_21;
+
+mod allocations;
+mod answers;
+mod args;
+mod bench;
+mod client;
+mod compare;
+mod days;
+mod fetch;
+mod report;
+mod submit;
+mod timing;
+mod verify;
+
+use std::env;
+use std::fs;
+use std::process;
+use std::time::Instant;
+use aoc_core::input::InputSource;
+use aoc_core::log;
+use aoc_core::params::Params;
+use aoc_core::parse::ParseError;
+use aoc_core::random::Rng;
+use aoc_core::svg::Style;
+use args::{Command, GenerateOptions, RunOptions};
+use report::{input_checksum, Record, Reporter};
+
+const USAGE: &str = "Usage:
+    aoc run --day N [--part P] [--input FILE|-] [--config FILE] [--format text|json|jsonl]
+            [--svg FILE [--cell-size N] [--colours FG,BG,PATH]]
+    aoc run --all [--part P] [--format text|json|jsonl]
+    aoc verify [--day N] [--answers FILE] [--timeout SECS]
+    aoc bench [--day N] [--part P] [--runs N] [--timeout SECS] [--baseline FILE] [--save FILE] [--threshold PCT]
+    aoc compare --day N [--inputs DIR|GLOB] [--answers FILE] [--timeout SECS]
+    aoc generate --day N [--seed S] [--size N]
+    aoc fetch --day N [--name FILE] [--cache DIR] [--base-url URL] [--session-file FILE]
+    aoc submit --day N --part P [--answer X] [--history FILE] [--wait] [--base-url URL] [--session-file FILE]
+
+Any command takes -v for solver diagnostics on stderr, or -vv to trace every step.";
+
+#[global_allocator]
+static ALLOCATOR: allocations::CountingAllocator = allocations::CountingAllocator;
+
+// `svg` is where to draw the day's picture, and how
+fn run_day(day: u32, parts: &[u32], source: &InputSource, config: Option<&str>, reporter: &mut Reporter, svg: Option<(&str, &Style)>) -> Result<(), String> {
+    let solution = days::solution(day).ok_or(format!("Day {} has no solution implemented", day))?;
+    let input = source.read().map_err(|e| format!("Couldn't read {}: {}", source, e))?;
+    let params = match config {
+        Some(path) => Params::load(path)?,
+        None => Params::for_input(source)?,
+    };
+
+    let checksum = input_checksum(&input);
+    // Errors in a parameter file already say which file they're in
+    let blame = |e: ParseError| match e.file {
+        Some(_) => e.to_string(),
+        None => format!("{}: {}", source, e),
+    };
+
+    for &part in parts {
+        let start = Instant::now();
+        let answer = solution.solve(part, &input, &params).map_err(blame)?;
+        reporter.report(Record { day, part, answer, elapsed: start.elapsed(), input_checksum: checksum.clone() });
+    }
+
+    if let Some((path, style)) = svg {
+        let picture = solution.picture(&input, &params).map_err(blame)?;
+        let picture = picture.ok_or(format!("Day {} has nothing to draw", day))?;
+        fs::write(path, picture.to_svg(style)).map_err(|e| format!("Couldn't write {}: {}", path, e))?;
+    }
+
+    Ok(())
+}
+
+fn run(options: RunOptions) -> Result<(), String> {
+    let parts = match options.part {
+        Some(part) => vec!(part),
+        None => vec!(1, 2),
+    };
+
+    let mut reporter = Reporter::new(options.format);
+
+    if options.all {
+        for day in days::solved_days() {
+            run_day(day, &parts, &InputSource::from_arg(None, days::input_path(day, "input.txt")), None, &mut reporter, None)?;
+        }
+    } else {
+        let day = options.day.unwrap();
+        let source = InputSource::from_arg(options.input.as_deref(), days::input_path(day, "input.txt"));
+        let svg = options.svg.as_deref().map(|path| (path, &options.style));
+        run_day(day, &parts, &source, options.config.as_deref(), &mut reporter, svg)?;
+    }
+
+    reporter.finish();
+    Ok(())
+}
+
+fn generate(options: GenerateOptions) -> Result<(), String> {
+    let solution = days::solution(options.day).ok_or(format!("Day {} has no solution implemented", options.day))?;
+    println!("{}", solution.generate(&mut Rng::new(options.seed), options.size));
+    Ok(())
+}
+
+fn exit_on_failure(all_ok: bool) {
+    if !all_ok {
+        process::exit(1);
+    }
+}
+
+fn main() {
+    let (level, args) = args::verbosity(&env::args().skip(1).collect::<Vec<String>>());
+    log::set_level(level);
+
+    let result = match args::parse(&args) {
+        Ok(Command::Run(options)) => run(options),
+        Ok(Command::Verify(options)) => verify::verify(&options).map(exit_on_failure),
+        Ok(Command::Bench(options)) => bench::bench(&options).map(exit_on_failure),
+        Ok(Command::Compare(options)) => compare::compare(&options).map(exit_on_failure),
+        Ok(Command::Generate(options)) => generate(options),
+        Ok(Command::Fetch(options)) => fetch::fetch(&options),
+        Ok(Command::Submit(options)) => submit::submit(&options).map(exit_on_failure),
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            process::exit(2);
+        }
+    };
+
+    if let Err(message) = result {
+        eprintln!("{}", message);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,83 @@
+use std::time::Duration;
+use serde_json::{json, Value};
+use aoc_core::solution::Answer;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+    JsonLines,
+}
+
+pub struct Record {
+    pub day: u32,
+    pub part: u32,
+    pub answer: Answer,
+    pub elapsed: Duration,
+    pub input_checksum: String,
+}
+
+impl Record {
+    pub fn to_json(&self) -> Value {
+        let answer = match self.answer {
+            Answer::Int(x) => json!(x),
+            _ => json!(self.answer.value()),
+        };
+
+        json!({
+            "day": self.day,
+            "part": self.part,
+            "answer": answer,
+            "type": self.answer.type_name(),
+            "elapsed_ms": self.elapsed.as_secs_f64() * 1000.0,
+            "input_checksum": self.input_checksum,
+        })
+    }
+}
+
+// The md5 of the normalised input, so results from the same puzzle input can be matched up
+pub fn input_checksum(input: &str) -> String {
+    format!("{:x}", md5::compute(input))
+}
+
+// Text and JSON-lines go out as each part finishes; a JSON document has to wait for the last one
+pub struct Reporter {
+    format: Format,
+    records: Vec<Value>,
+}
+
+impl Reporter {
+    pub fn new(format: Format) -> Reporter {
+        Reporter { format, records: Vec::new() }
+    }
+
+    pub fn report(&mut self, record: Record) {
+        match self.format {
+            Format::Text => println!("Day {} part {}: {}", record.day, record.part, record.answer),
+            Format::JsonLines => println!("{}", record.to_json()),
+            Format::Json => self.records.push(record.to_json()),
+        }
+    }
+
+    pub fn finish(self) {
+        if self.format == Format::Json {
+            println!("{}", serde_json::to_string_pretty(&json!({ "results": self.records })).unwrap());
+        }
+    }
+}
+
+#[test]
+fn json_records() {
+    let record = Record { day: 8, part: 1, answer: Answer::Int(123), elapsed: Duration::from_micros(1500), input_checksum: input_checksum("rect 3x2") };
+    assert_eq!(json!({"day": 8, "part": 1, "answer": 123, "type": "int", "elapsed_ms": 1.5, "input_checksum": input_checksum("rect 3x2")}), record.to_json());
+
+    let record = Record { answer: Answer::Art("#.\n.#".to_string()), ..record };
+    assert_eq!(json!("#.\n.#"), record.to_json()["answer"]);
+    assert_eq!(json!("art"), record.to_json()["type"]);
+}
+
+#[test]
+fn checksums() {
+    assert_eq!("d41d8cd98f00b204e9800998ecf8427e", input_checksum(""));
+    assert_eq!(32, input_checksum("ffykfhsq").len());
+}
@@ -0,0 +1,255 @@
+use std::fs;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use serde::{Deserialize, Serialize};
+use aoc_core::input::InputSource;
+use aoc_core::params::Params;
+use aoc_core::solution::Answer;
+use crate::args::SubmitOptions;
+use crate::client::{base_url, session_token, Client};
+use crate::days;
+
+pub const DEFAULT_HISTORY: &str = "submissions.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+    Wait,
+}
+
+impl Outcome {
+    fn describe(&self) -> &'static str {
+        match *self {
+            Outcome::Right => "right",
+            Outcome::Wrong => "wrong",
+            Outcome::TooHigh => "too high",
+            Outcome::TooLow => "too low",
+            Outcome::Wait => "not checked, still cooling down",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Attempt {
+    pub day: u32,
+    pub part: u32,
+    pub answer: String,
+    pub outcome: Outcome,
+    pub at: u64,
+}
+
+// Every answer we've sent, plus when the site will next accept one (seconds since the epoch)
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct History {
+    pub attempts: Vec<Attempt>,
+    pub wait_until: u64,
+}
+
+impl History {
+    fn load(path: &str) -> Result<History, String> {
+        match fs::read_to_string(path) {
+            Ok(text) => serde_json::from_str(&text).map_err(|e| format!("{}: {}", path, e)),
+            Err(_) => Ok(History::default()),
+        }
+    }
+
+    fn save(&self, path: &str) -> Result<(), String> {
+        let text = serde_json::to_string_pretty(self).unwrap();
+        fs::write(path, text + "\n").map_err(|e| format!("Couldn't write {}: {}", path, e))
+    }
+
+    // Why `answer` shouldn't be sent, if the history already rules it out. A numeric answer is also
+    // ruled out when it lies beyond an earlier "too high" or "too low".
+    pub fn refusal(&self, day: u32, part: u32, answer: &str) -> Option<String> {
+        let number = answer.parse::<i64>().ok();
+
+        self.attempts.iter().filter(|a| a.day == day && a.part == part).find_map(|a| {
+            let earlier = a.answer.parse::<i64>().ok();
+            match (a.outcome, number, earlier) {
+                (Outcome::Right, _, _) => Some(format!("Day {} part {} was already solved with {}", day, part, a.answer)),
+                (Outcome::Wait, _, _) => None,
+                (_, _, _) if a.answer == answer => Some(format!("{} was already rejected as {}", answer, a.outcome.describe())),
+                (Outcome::TooHigh, Some(x), Some(high)) if x >= high => Some(format!("{} can't be right, {} was too high", answer, high)),
+                (Outcome::TooLow, Some(x), Some(low)) if x <= low => Some(format!("{} can't be right, {} was too low", answer, low)),
+                _ => None,
+            }
+        })
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs()
+}
+
+// Reads durations like "1m 24s" or "36s"
+fn parse_duration(text: &str) -> Option<u64> {
+    text.split_whitespace().map(|part| {
+        let (number, unit) = part.split_at(part.len() - 1);
+        let number = number.parse::<u64>().ok()?;
+        match unit {
+            "h" => Some(number * 3600),
+            "m" => Some(number * 60),
+            "s" => Some(number),
+            _ => None,
+        }
+    }).sum()
+}
+
+// How long the site asks us to hold off before the next answer, in seconds
+fn parse_wait(text: &str) -> Option<u64> {
+    if let Some((_, rest)) = text.split_once("You have ") {
+        return rest.split_once(" left to wait").and_then(|(duration, _)| parse_duration(duration));
+    }
+
+    let (_, rest) = text.split_once("wait ")?;
+    let mut words = rest.split_whitespace();
+    let count = match words.next()? {
+        "one" | "a" => 1,
+        number => number.parse().ok()?,
+    };
+    match words.next()? {
+        unit if unit.starts_with("minute") => Some(count * 60),
+        unit if unit.starts_with("second") => Some(count),
+        _ => None,
+    }
+}
+
+// Pulls the verdict and any cooldown out of the page the site returns for an answer
+pub fn parse_response(page: &str) -> Result<(Outcome, Option<u64>), String> {
+    let outcome = match page {
+        _ if page.contains("That's the right answer") => Outcome::Right,
+        _ if page.contains("You gave an answer too recently") => Outcome::Wait,
+        _ if page.contains("your answer is too high") => Outcome::TooHigh,
+        _ if page.contains("your answer is too low") => Outcome::TooLow,
+        _ if page.contains("That's not the right answer") => Outcome::Wrong,
+        _ if page.contains("You don't seem to be solving the right level") =>
+            return Err("The site isn't accepting answers for that part; is it already solved or still locked?".to_string()),
+        _ => return Err(format!("Couldn't make sense of the response: {:?}", page.chars().take(200).collect::<String>())),
+    };
+
+    let wait = match outcome {
+        Outcome::Right => None,
+        _ => parse_wait(page),
+    };
+    Ok((outcome, wait))
+}
+
+// Sends one answer unless the history already rules it out, and records what the site said
+pub fn submit_answer(client: &Client, history: &mut History, day: u32, part: u32, answer: &str) -> Result<Outcome, String> {
+    if let Some(reason) = history.refusal(day, part, answer) {
+        return Err(format!("Not submitting: {}", reason));
+    }
+    if history.wait_until > now() {
+        return Err(format!("Not submitting: the site wants us to wait another {}s", history.wait_until - now()));
+    }
+
+    let form = [("level", part.to_string()), ("answer", answer.to_string())];
+    let page = client.post(&client.day_url(day, "/answer"), &form)?;
+    let (outcome, wait) = parse_response(&page)?;
+
+    history.attempts.push(Attempt { day, part, answer: answer.to_string(), outcome, at: now() });
+    history.wait_until = now() + wait.unwrap_or(0);
+    Ok(outcome)
+}
+
+fn solve(day: u32, part: u32) -> Result<String, String> {
+    let solution = days::solution(day).ok_or(format!("Day {} has no solution implemented", day))?;
+    let source = InputSource::File(days::input_path(day, "input.txt").into());
+    let input = source.read().map_err(|e| format!("Couldn't read {}: {}", source, e))?;
+    let params = Params::for_input(&source)?;
+
+    match solution.solve(part, &input, &params).map_err(|e| format!("{}: {}", source, e))? {
+        Answer::Art(art) => Err(format!("Day {} part {} draws its answer:\n{}\nRead off the letters and pass them with --answer", day, part, art)),
+        answer => Ok(answer.value()),
+    }
+}
+
+// Returns whether the answer was accepted
+pub fn submit(options: &SubmitOptions) -> Result<bool, String> {
+    let answer = match options.answer {
+        Some(ref answer) => answer.clone(),
+        None => solve(options.day, options.part)?,
+    };
+
+    let mut history = History::load(&options.history)?;
+    if options.wait && history.wait_until > now() {
+        let seconds = history.wait_until - now();
+        println!("Waiting {}s for the cooldown to pass", seconds);
+        thread::sleep(Duration::from_secs(seconds));
+    }
+
+    let client = Client::new(&base_url(options.base_url.as_deref()), &session_token(options.session_file.as_deref())?);
+    let result = submit_answer(&client, &mut history, options.day, options.part, &answer);
+    history.save(&options.history)?;
+
+    let outcome = result?;
+    println!("Day {} part {}: {} is {}", options.day, options.part, answer, outcome.describe());
+    if history.wait_until > now() {
+        println!("The next answer can be sent in {}s", history.wait_until - now());
+    }
+    Ok(outcome == Outcome::Right)
+}
+
+#[test]
+fn responses() {
+    let right = "<article><p>That's the right answer!  You are one gold star closer to fixing the sleigh.</p></article>";
+    let high = "<article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data. Please wait one minute before trying again. [<a href=\"/2016/day/3\">Return to Day 3</a>]</p></article>";
+    let low = "<p>That's not the right answer; your answer is too low.  Please wait 5 minutes before trying again.</p>";
+    let wrong = "<p>That's not the right answer.  If you're stuck, make sure you're using the full input data.</p>";
+    let wait = "<p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 24s left to wait.</p>";
+
+    assert_eq!(Ok((Outcome::Right, None)), parse_response(right));
+    assert_eq!(Ok((Outcome::TooHigh, Some(60))), parse_response(high));
+    assert_eq!(Ok((Outcome::TooLow, Some(300))), parse_response(low));
+    assert_eq!(Ok((Outcome::Wrong, None)), parse_response(wrong));
+    assert_eq!(Ok((Outcome::Wait, Some(84))), parse_response(wait));
+    assert!(parse_response("<p>You don't seem to be solving the right level.  Did you already complete it?</p>").is_err());
+    assert!(parse_response("<html>502 Bad Gateway</html>").is_err());
+}
+
+#[test]
+fn refusals() {
+    let attempt = |part, answer: &str, outcome| Attempt { day: 3, part, answer: answer.to_string(), outcome, at: 0 };
+    let history = History {
+        attempts: vec!(attempt(1, "900", Outcome::TooHigh), attempt(1, "800", Outcome::TooLow), attempt(1, "850", Outcome::Wait), attempt(2, "1544", Outcome::Right)),
+        wait_until: 0,
+    };
+
+    assert_eq!(Some("900 was already rejected as too high".to_string()), history.refusal(3, 1, "900"));
+    assert_eq!(Some("950 can't be right, 900 was too high".to_string()), history.refusal(3, 1, "950"));
+    assert_eq!(Some("12 can't be right, 800 was too low".to_string()), history.refusal(3, 1, "12"));
+    assert_eq!(None, history.refusal(3, 1, "850"));
+    assert_eq!(None, history.refusal(3, 1, "869"));
+    assert_eq!(Some("Day 3 part 2 was already solved with 1544".to_string()), history.refusal(3, 2, "1545"));
+    assert_eq!(None, history.refusal(4, 1, "900"));
+}
+
+#[test]
+fn submits_against_stand_in() {
+    use crate::client::stand_in_server;
+
+    let (base_url, requests) = stand_in_server(|request| match request.ends_with("answer=869") {
+        true => (200, "<p>That's the right answer!</p>".to_string()),
+        false => (200, "<p>That's not the right answer; your answer is too high.</p>".to_string()),
+    });
+    let client = Client::new(&base_url, "abc123");
+    let mut history = History::default();
+
+    assert_eq!(Ok(Outcome::TooHigh), submit_answer(&client, &mut history, 3, 1, "900"));
+    assert!(submit_answer(&client, &mut history, 3, 1, "901").is_err());
+    assert_eq!(Ok(Outcome::Right), submit_answer(&client, &mut history, 3, 1, "869"));
+    assert!(submit_answer(&client, &mut history, 3, 1, "869").is_err());
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(2, requests.len());
+    assert!(requests[0].starts_with("POST /2016/day/3/answer "));
+    assert!(requests[0].ends_with("\r\n\r\nlevel=1&answer=900"));
+    assert_eq!(vec!(Outcome::TooHigh, Outcome::Right), history.attempts.iter().map(|a| a.outcome).collect::<Vec<Outcome>>());
+
+    history.wait_until = now() + 60;
+    assert!(submit_answer(&client, &mut history, 3, 2, "1544").unwrap_err().contains("wait another"));
+}
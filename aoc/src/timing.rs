@@ -0,0 +1,118 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+use aoc_core::params::Params;
+use aoc_core::solution::Answer;
+use crate::allocations;
+use crate::days;
+
+// Solver threads that haven't finished, including any abandoned after a timeout
+static RUNNING: AtomicUsize = AtomicUsize::new(0);
+
+struct Running;
+
+impl Running {
+    fn start() -> Running {
+        RUNNING.fetch_add(1, Ordering::SeqCst);
+        Running
+    }
+}
+
+impl Drop for Running {
+    fn drop(&mut self) {
+        RUNNING.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+pub struct Run {
+    pub result: Result<Answer, String>,
+    pub elapsed: Duration,
+    pub peak_bytes: usize,
+    // False when an abandoned solver was still running alongside, taking CPU time and
+    // allocating, so the time is inflated and the peak isn't measured at all
+    pub reliable: bool,
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let millis = duration.as_secs_f64() * 1000.0;
+    match millis >= 1000.0 {
+        true => format!("{:.2}s", millis / 1000.0),
+        false => format!("{:.2}ms", millis),
+    }
+}
+
+pub fn format_bytes(bytes: usize) -> String {
+    match bytes {
+        x if x >= 1 << 20 => format!("{:.1}MB", x as f64 / (1 << 20) as f64),
+        x if x >= 1 << 10 => format!("{:.1}KB", x as f64 / (1 << 10) as f64),
+        x => format!("{}B", x),
+    }
+}
+
+// Solvers run on their own thread so one that never finishes can be abandoned after the
+// timeout, in which case there's no run to report. Threads can't be stopped, so an abandoned one
+// keeps running and every later run is marked unreliable until it finishes.
+pub fn timed_solve(day: u32, part: u32, input: &str, params: &Params, timeout: Duration) -> Option<Run> {
+    let (sender, receiver) = mpsc::channel();
+    let input = input.to_string();
+    let params = params.clone();
+    let reliable = RUNNING.load(Ordering::SeqCst) == 0;
+    let running = Running::start();
+
+    thread::spawn(move || {
+        let running = running;
+        let solution = days::solution(day).unwrap();
+        let live_bytes = match reliable {
+            true => allocations::reset_peak(),
+            false => 0,
+        };
+        let start = Instant::now();
+        let result = solution.solve(part, &input, &params).map_err(|e| e.to_string());
+        let elapsed = start.elapsed();
+        let peak_bytes = match reliable {
+            true => allocations::peak().saturating_sub(live_bytes),
+            false => 0,
+        };
+        // Finished before saying so, so the next solve doesn't find this one still running
+        drop(running);
+        let _ = sender.send(Run { result, elapsed, peak_bytes, reliable });
+    });
+
+    match receiver.recv_timeout(timeout) {
+        Ok(run) => Some(run),
+        Err(mpsc::RecvTimeoutError::Timeout) => None,
+        Err(mpsc::RecvTimeoutError::Disconnected) => Some(Run { result: Err("solver panicked".to_string()), elapsed: Duration::from_secs(0), peak_bytes: 0, reliable }),
+    }
+}
+
+#[test]
+fn durations() {
+    assert_eq!("0.25ms", format_duration(Duration::from_micros(250)));
+    assert_eq!("12.50s", format_duration(Duration::from_millis(12500)));
+}
+
+#[test]
+fn byte_counts() {
+    assert_eq!("512B", format_bytes(512));
+    assert_eq!("1.5KB", format_bytes(1536));
+    assert_eq!("3.0MB", format_bytes(3 << 20));
+}
+
+#[test]
+fn abandoned_solvers() {
+    // Solves that finish in time leave nothing running, however quickly the next one starts. This
+    // has to come first, as the abandoned solver below keeps running for the rest of the tests.
+    for part in [1, 2, 1, 2] {
+        let run = timed_solve(1, part, "R8, R4, R4, R8", &Params::new(), Duration::from_secs(10)).unwrap();
+        assert!(run.result.is_ok());
+        assert!(run.reliable);
+    }
+
+    // Day 5 hashes for far longer than no time at all, so it's abandoned and still running for the next solve
+    assert!(timed_solve(5, 1, "abc", &Params::new(), Duration::from_secs(0)).is_none());
+    let run = timed_solve(1, 1, "R2", &Params::new(), Duration::from_secs(10)).unwrap();
+    assert_eq!(Ok(Answer::Int(2)), run.result);
+    assert!(!run.reliable);
+    assert_eq!(0, run.peak_bytes);
+}
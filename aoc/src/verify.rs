@@ -0,0 +1,129 @@
+use std::path::Path;
+use std::time::Duration;
+use aoc_core::input::InputSource;
+use aoc_core::params::Params;
+use aoc_core::solution::Answer;
+use crate::answers::Answers;
+use crate::args::VerifyOptions;
+use crate::days;
+use crate::timing::{format_duration, timed_solve};
+
+#[derive(Debug, PartialEq)]
+pub enum Status {
+    Pass,
+    Fail { expected: String, actual: String },
+    Missing { actual: String },
+    Error(String),
+    Timeout,
+}
+
+pub struct Check {
+    pub day: u32,
+    pub part: u32,
+    pub input_name: String,
+    pub status: Status,
+    pub elapsed: Duration,
+    // False when a solver that timed out earlier was still running, slowing this one down
+    pub reliable: bool,
+}
+
+impl Check {
+    pub fn is_ok(&self) -> bool {
+        matches!(self.status, Status::Pass | Status::Missing { .. })
+    }
+
+    fn describe(&self) -> String {
+        let (label, detail) = match self.status {
+            Status::Pass => ("pass", String::new()),
+            Status::Fail { ref expected, ref actual } => ("FAIL", format!("expected {:?}, got {:?}", expected, actual)),
+            Status::Missing { ref actual } => ("missing", format!("got {:?}", actual)),
+            Status::Error(ref message) => ("ERROR", message.clone()),
+            Status::Timeout => ("TIMEOUT", String::new()),
+        };
+        let detail = match self.reliable {
+            true => detail,
+            false => format!("{}  (time unreliable, a timed-out solver was still running)", detail).trim_start().to_string(),
+        };
+
+        format!("Day {:>2} part {} {:<20} {:<8} {:>10}  {}", self.day, self.part, self.input_name, label, format_duration(self.elapsed), detail)
+            .trim_end().to_string()
+    }
+}
+
+pub fn check_status(expected: Option<&str>, result: Result<Answer, String>) -> Status {
+    match (expected, result) {
+        (_, Err(message)) => Status::Error(message),
+        (None, Ok(answer)) => Status::Missing { actual: answer.value() },
+        (Some(expected), Ok(ref answer)) if answer.value() == expected => Status::Pass,
+        (Some(expected), Ok(answer)) => Status::Fail { expected: expected.to_string(), actual: answer.value() },
+    }
+}
+
+// Solves both parts for one input file, checking them against the answers recorded under `input_name`
+pub fn check_input(day: u32, path: &Path, input_name: &str, answers: &Answers, timeout: Duration) -> Vec<Check> {
+    let source = InputSource::File(path.to_path_buf());
+    let input = source.read().map_err(|e| format!("couldn't read {}: {}", source, e))
+        .and_then(|input| Ok((input, Params::for_input(&source)?)));
+
+    (1..3).map(|part| {
+        let (status, elapsed, reliable) = match input {
+            Ok((ref input, ref params)) => {
+                match timed_solve(day, part, input, params, timeout) {
+                    Some(run) => (check_status(answers.expected(day, input_name, part), run.result), run.elapsed, run.reliable),
+                    None => (Status::Timeout, timeout, true),
+                }
+            },
+            Err(ref message) => (Status::Error(message.clone()), Duration::from_secs(0), true),
+        };
+
+        Check { day, part, input_name: input_name.to_string(), status, elapsed, reliable }
+    }).collect()
+}
+
+// Returns whether every check passed or only lacked a recorded answer
+pub fn verify(options: &VerifyOptions) -> Result<bool, String> {
+    let answers = Answers::load(&options.answers)?;
+    let timeout = Duration::from_secs(options.timeout);
+
+    let days = match options.day {
+        Some(day) if days::solution(day).is_none() => return Err(format!("Day {} has no solution implemented", day)),
+        Some(day) => vec!(day),
+        None => days::solved_days(),
+    };
+
+    let mut checks = Vec::new();
+    for day in days {
+        let mut input_names = vec!("input.txt".to_string());
+        for name in answers.input_names(day) {
+            if !input_names.contains(&name) {
+                input_names.push(name);
+            }
+        }
+
+        for input_name in input_names {
+            for check in check_input(day, days::input_path(day, &input_name).as_ref(), &input_name, &answers, timeout) {
+                println!("{}", check.describe());
+                checks.push(check);
+            }
+        }
+    }
+
+    let count = |matches: &dyn Fn(&Status) -> bool| checks.iter().filter(|check| matches(&check.status)).count();
+    println!("\n{} passed, {} failed, {} missing, {} errors, {} timed out",
+             count(&|s| *s == Status::Pass),
+             count(&|s| matches!(*s, Status::Fail { .. })),
+             count(&|s| matches!(*s, Status::Missing { .. })),
+             count(&|s| matches!(*s, Status::Error(_))),
+             count(&|s| *s == Status::Timeout));
+
+    Ok(checks.iter().all(|check| check.is_ok()))
+}
+
+#[test]
+fn statuses() {
+    assert_eq!(Status::Pass, check_status(Some("236"), Ok(Answer::Int(236))));
+    assert_eq!(Status::Pass, check_status(Some("#.\n.#"), Ok(Answer::Art("#.\n.#".to_string()))));
+    assert_eq!(Status::Fail { expected: "236".to_string(), actual: "235".to_string() }, check_status(Some("236"), Ok(Answer::Int(235))));
+    assert_eq!(Status::Missing { actual: "A6B35".to_string() }, check_status(None, Ok(Answer::Text("A6B35".to_string()))));
+    assert_eq!(Status::Error("line 1".to_string()), check_status(Some("1"), Err("line 1".to_string())));
+}
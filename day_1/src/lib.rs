@@ -0,0 +1,474 @@
+extern crate aoc_core;
+
+use std::cmp;
+use std::collections::HashSet;
+use std::str::FromStr;
+use aoc_core::grid::Point;
+use aoc_core::parse::{ParseError, token};
+use aoc_core::params::Params;
+use aoc_core::random::Rng;
+use aoc_core::solution::{Answer, Solution};
+use aoc_core::svg::Picture;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Heading {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest
+}
+
+// Clockwise, an eighth of a turn apart
+const HEADINGS: [Heading; 8] = [Heading::North, Heading::NorthEast, Heading::East, Heading::SouthEast,
+                                Heading::South, Heading::SouthWest, Heading::West, Heading::NorthWest];
+
+impl Heading {
+    fn turn(self, eighths: i32) -> Heading {
+        let index = HEADINGS.iter().position(|&heading| heading == self).unwrap() as i32;
+        HEADINGS[(index + eighths).rem_euclid(8) as usize]
+    }
+
+    fn step(&self) -> (i64, i64) {
+        match *self {
+            Heading::North => (0, 1),
+            Heading::NorthEast => (1, 1),
+            Heading::East => (1, 0),
+            Heading::SouthEast => (1, -1),
+            Heading::South => (0, -1),
+            Heading::SouthWest => (-1, -1),
+            Heading::West => (-1, 0),
+            Heading::NorthWest => (-1, 1)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Move {
+    // Eighths of a turn clockwise from the current heading
+    Turn(i32),
+    Face(Heading),
+}
+
+// Every word an instruction can start with. Variants of the puzzle add theirs here.
+const MOVES: &[(&str, Move)] = &[
+    ("R", Move::Turn(2)),
+    ("L", Move::Turn(-2)),
+    ("B", Move::Turn(4)),
+    ("N", Move::Face(Heading::North)),
+    ("NE", Move::Face(Heading::NorthEast)),
+    ("E", Move::Face(Heading::East)),
+    ("SE", Move::Face(Heading::SouthEast)),
+    ("S", Move::Face(Heading::South)),
+    ("SW", Move::Face(Heading::SouthWest)),
+    ("W", Move::Face(Heading::West)),
+    ("NW", Move::Face(Heading::NorthWest)),
+];
+
+struct Instruction<'a>(Move, u32, &'a str);
+
+// How far from the start the walk ends up, chosen with the `metric` parameter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    Manhattan,
+    Chebyshev,
+    Euclidean
+}
+
+impl Metric {
+    pub fn distance(&self, (x, y): Point) -> f64 {
+        let (x, y) = ((x as f64).abs(), (y as f64).abs());
+        match *self {
+            Metric::Manhattan => x + y,
+            Metric::Chebyshev => x.max(y),
+            Metric::Euclidean => x.hypot(y),
+        }
+    }
+
+    // Whole numbers for the metrics that only give whole numbers, three decimal places otherwise
+    fn answer(&self, distance: f64) -> Answer {
+        match *self {
+            Metric::Euclidean => format!("{:.3}", distance).into(),
+            _ => (distance as i64).into(),
+        }
+    }
+}
+
+impl FromStr for Metric {
+    type Err = ();
+
+    fn from_str(name: &str) -> Result<Metric, ()> {
+        match name {
+            "manhattan" => Ok(Metric::Manhattan),
+            "chebyshev" => Ok(Metric::Chebyshev),
+            "euclidean" => Ok(Metric::Euclidean),
+            _ => Err(()),
+        }
+    }
+}
+
+type Vector = (i64, i64);
+
+fn cross(a: Vector, b: Vector) -> i64 {
+    a.0 * b.1 - a.1 * b.0
+}
+
+// One instruction's straight stretch of the walk, both ends included. It runs along an axis or a diagonal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Segment {
+    pub start: Point,
+    pub end: Point,
+}
+
+impl Segment {
+    fn offset(&self, to: Point) -> Vector {
+        (to.0 as i64 - self.start.0 as i64, to.1 as i64 - self.start.1 as i64)
+    }
+
+    fn direction(&self) -> Vector {
+        let (x, y) = self.offset(self.end);
+        (x.signum(), y.signum())
+    }
+
+    // The number of steps, where a diagonal step counts as one
+    pub fn length(&self) -> i64 {
+        let (x, y) = self.offset(self.end);
+        cmp::max(x.abs(), y.abs())
+    }
+
+    fn point_at(&self, step: i64) -> Point {
+        let (dx, dy) = self.direction();
+        ((self.start.0 as i64 + dx * step) as i32, (self.start.1 as i64 + dy * step) as i32)
+    }
+
+    // The steps along this segment, from 1 to its length, that land on `other`
+    fn steps_onto(&self, other: &Segment) -> Option<(i64, i64)> {
+        let (direction, other_direction) = (self.direction(), other.direction());
+        let offset = self.offset(other.start);
+
+        let (first, last) = match cross(direction, other_direction) {
+            0 => {
+                // Parallel, or `other` is a single point, so only the part lying on this segment's line counts
+                if cross(offset, direction) != 0 {
+                    return None;
+                }
+                let along = |(x, y): Vector| if direction.0 != 0 { x * direction.0 } else { y * direction.1 };
+                let (from, to) = (along(offset), along(self.offset(other.end)));
+                (cmp::min(from, to), cmp::max(from, to))
+            },
+            denominator => {
+                // Diagonals can cross between points, where neither walk actually steps
+                let (step, other_step) = (cross(offset, other_direction), cross(offset, direction));
+                if step % denominator != 0 || other_step % denominator != 0 || !(0..=other.length()).contains(&(other_step / denominator)) {
+                    return None;
+                }
+                (step / denominator, step / denominator)
+            },
+        };
+
+        let (first, last) = (cmp::max(1, first), cmp::min(self.length(), last));
+        match first <= last {
+            true => Some((first, last)),
+            false => None,
+        }
+    }
+}
+
+// The walk as one segment per instruction, so its queries cost the same however far each
+// instruction goes. Comparing segments makes `crossings` quadratic in the number of instructions.
+pub struct Path(Vec<Segment>);
+
+impl Path {
+    pub fn segments(&self) -> &[Segment] {
+        &self.0
+    }
+
+    pub fn end(&self) -> Point {
+        self.0.last().map_or((0, 0), |segment| segment.end)
+    }
+
+    // The start followed by the end of every segment
+    pub fn corners(&self) -> Vec<Point> {
+        std::iter::once((0, 0)).chain(self.0.iter().map(|segment| segment.end)).collect()
+    }
+
+    pub fn length(&self) -> i64 {
+        self.0.iter().map(Segment::length).sum()
+    }
+
+    // The lowest and highest corners of the smallest box around the walk
+    pub fn bounding_box(&self) -> (Point, Point) {
+        self.corners().iter().fold(((0, 0), (0, 0)), |(low, high), &(x, y)| {
+            ((cmp::min(low.0, x), cmp::min(low.1, y)), (cmp::max(high.0, x), cmp::max(high.1, y)))
+        })
+    }
+
+    // The runs of steps along segment `index` that go over ground an earlier segment covered, in order
+    fn retraced(&self, index: usize) -> Vec<(i64, i64)> {
+        let segment = &self.0[index];
+        let mut runs = self.0[..index].iter().filter_map(|earlier| segment.steps_onto(earlier)).collect::<Vec<(i64, i64)>>();
+        runs.sort();
+
+        let mut merged: Vec<(i64, i64)> = Vec::new();
+        for (first, last) in runs {
+            match merged.last_mut() {
+                Some(run) if first <= run.1 + 1 => run.1 = cmp::max(run.1, last),
+                _ => merged.push((first, last)),
+            }
+        }
+        merged
+    }
+
+    // Remembering every point stepped on is linear in the length of the walk, and comparing segments
+    // is quadratic in the number of instructions, so this takes whichever is less work
+    pub fn first_crossing(&self) -> Option<Point> {
+        let segments = self.0.len() as i64;
+        match self.length() <= segments.saturating_mul(segments) {
+            true => self.first_crossing_by_steps(),
+            false => self.first_crossing_by_segments(),
+        }
+    }
+
+    fn first_crossing_by_steps(&self) -> Option<Point> {
+        let mut visited = HashSet::from([(0, 0)]);
+        self.0.iter().flat_map(|segment| (1..=segment.length()).map(|step| segment.point_at(step))).find(|&point| !visited.insert(point))
+    }
+
+    fn first_crossing_by_segments(&self) -> Option<Point> {
+        (0..self.0.len()).find_map(|index| self.retraced(index).first().map(|&(first, _)| self.0[index].point_at(first)))
+    }
+
+    // Every stretch of the walk that goes back over earlier ground, in the order it's walked.
+    // A single point where the walk crosses itself is a segment of length zero.
+    pub fn crossings(&self) -> Vec<Segment> {
+        (0..self.0.len()).flat_map(|index| {
+            let segment = self.0[index];
+            self.retraced(index).into_iter().map(move |(first, last)| Segment { start: segment.point_at(first), end: segment.point_at(last) })
+        }).collect()
+    }
+}
+
+fn parse_instruction<'a>(line: &str, instruction: &'a str) -> Result<Instruction<'a>, ParseError> {
+    let word_end = instruction.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(instruction.len());
+    let (word, dist) = instruction.split_at(word_end);
+    let step = match MOVES.iter().find(|&&(name, _)| name == word) {
+        Some(&(_, step)) => step,
+        None => return Err(ParseError::at(line, word, "a move such as R, L, B, N or NE"))
+    };
+
+    Ok(Instruction(step, token(line, dist, "a distance")?, dist))
+}
+
+pub fn get_path(instructions: &str) -> Result<Path, ParseError> {
+    let line = instructions.trim();
+    let steps = line.split(", ").map(|d| parse_instruction(line, d)).collect::<Result<Vec<Instruction>, ParseError>>()?;
+
+    let mut heading = Heading::North;
+    let mut segments = Vec::new();
+    let mut position = (0, 0);
+    for Instruction(step, dist, dist_token) in steps {
+        heading = match step {
+            Move::Turn(eighths) => heading.turn(eighths),
+            Move::Face(new_heading) => new_heading,
+        };
+        let (step_x, step_y) = heading.step();
+        let end = (i32::try_from(position.0 as i64 + step_x * dist as i64), i32::try_from(position.1 as i64 + step_y * dist as i64));
+        let end = match end {
+            (Ok(x), Ok(y)) => (x, y),
+            _ => return Err(ParseError::at(line, dist_token, "a distance that keeps the walk in range")),
+        };
+
+        segments.push(Segment { start: position, end });
+        position = end;
+    }
+
+    Ok(Path(segments))
+}
+
+pub fn calculate_final_distance(path_taken: &Path, metric: Metric) -> (Point,f64) {
+    let final_location = path_taken.end();
+    (final_location, metric.distance(final_location))
+}
+
+// The first point stepped on for a second time
+pub fn calculate_first_intersect(path_taken: &Path, metric: Metric) -> Option<(Point,f64)> {
+    let first_intersect = path_taken.first_crossing()?;
+    Some((first_intersect, metric.distance(first_intersect)))
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    fn part_one(&self, input: &str, params: &Params) -> Result<Answer, ParseError> {
+        let metric = params.get("metric", Metric::Manhattan)?;
+        Ok(metric.answer(calculate_final_distance(&get_path(input)?, metric).1))
+    }
+
+    fn part_two(&self, input: &str, params: &Params) -> Result<Answer, ParseError> {
+        let metric = params.get("metric", Metric::Manhattan)?;
+        let line = input.trim();
+        match calculate_first_intersect(&get_path(input)?, metric) {
+            Some((_, distance)) => Ok(metric.answer(distance)),
+            None => Err(ParseError::at(line, line, "a walk that crosses its own path")),
+        }
+    }
+
+    // `size` turns, ending in a square of R1s so part two always has a crossing to find
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        let turns = (0..size).map(|_| {
+            let distance = match rng.one_in(10) {
+                true => rng.range(0, 200),
+                false => rng.range(0, 12),
+            };
+            format!("{}{}", rng.pick(&["L", "R"]), distance)
+        });
+        turns.chain(vec!("R1".to_string(); 4)).collect::<Vec<String>>().join(", ")
+    }
+
+    // The walk from the start, with north at the top
+    fn picture(&self, input: &str, _params: &Params) -> Result<Option<Picture>, ParseError> {
+        Ok(Some(Picture::path(get_path(input)?.corners().into_iter().map(|(x, y)| (x, -y)).collect())))
+    }
+}
+
+#[test]
+fn part_one() {
+    let inputs = "R5, L5, R5, R3";
+    assert_eq!(((10,2), 12.0), calculate_final_distance(&get_path(inputs).unwrap(), Metric::Manhattan));
+    let inputs = "R2, R2, R2";
+    assert_eq!(((0,-2), 2.0), calculate_final_distance(&get_path(inputs).unwrap(), Metric::Manhattan));
+    let inputs = "R2, L3";
+    assert_eq!(((2,3), 5.0), calculate_final_distance(&get_path(inputs).unwrap(), Metric::Manhattan));
+}
+
+#[test]
+fn part_two() {
+    let inputs = "R8, R4, R4, R8";
+    assert_eq!(Some(((4,0), 4.0)), calculate_first_intersect(&get_path(inputs).unwrap(), Metric::Manhattan));
+    // The start is crossed again too, but only after (2,0)
+    let inputs = "R3, L1, L1, L1, R2";
+    assert_eq!(Some(((2,0), 2.0)), calculate_first_intersect(&get_path(inputs).unwrap(), Metric::Manhattan));
+
+    // A walk that never crosses itself has no answer rather than a panic
+    assert_eq!(None, calculate_first_intersect(&get_path("L2, R7").unwrap(), Metric::Manhattan));
+    assert_eq!(Err(ParseError::new(1, 1, "L2, R7", "a walk that crosses its own path")), Day1.part_two("L2, R7\n", &Params::new()));
+}
+
+#[test]
+fn bad_instructions() {
+    assert_eq!(Err(ParseError::new(1, 5, "X", "a move such as R, L, B, N or NE")), get_path("R5, X5").map(|_| ()));
+    assert_eq!(Err(ParseError::new(1, 6, "", "a distance")), get_path("R5, L").map(|_| ()));
+    assert_eq!(Err(ParseError::new(1, 1, "", "a move such as R, L, B, N or NE")), get_path("").map(|_| ()));
+    assert_eq!(Err(ParseError::new(1, 6, "-5", "a distance")), get_path("R5, L-5").map(|_| ()));
+}
+
+#[test]
+fn mirrored_paths() {
+    // Swapping every L and R reflects the walk, which keeps both distances
+    for seed in 0..20 {
+        let input = Day1.generate(&mut Rng::new(seed), 30);
+        let mirrored = input.replace('L', "x").replace('R', "L").replace('x', "R");
+        let (path, mirrored_path) = (get_path(&input).unwrap(), get_path(&mirrored).unwrap());
+
+        assert_eq!(calculate_final_distance(&path, Metric::Manhattan).1, calculate_final_distance(&mirrored_path, Metric::Manhattan).1);
+        assert_eq!(calculate_first_intersect(&path, Metric::Manhattan).map(|(_, d)| d), calculate_first_intersect(&mirrored_path, Metric::Manhattan).map(|(_, d)| d));
+    }
+}
+
+#[test]
+fn long_walks() {
+    // A billion steps east take no more room than one
+    let path = get_path("R1000000000, L5, L3, L10").unwrap();
+    assert_eq!(((999999997,-5), 1000000002.0), calculate_final_distance(&path, Metric::Manhattan));
+    assert_eq!(Some(((999999997,0), 999999997.0)), calculate_first_intersect(&path, Metric::Manhattan));
+    assert_eq!(1000000018, path.length());
+    assert_eq!(((0,-5), (1000000000,5)), path.bounding_box());
+    assert_eq!(vec![Segment { start: (999999997,0), end: (999999997,0) }], path.crossings());
+
+    // A staircase of 400,000 instructions, far too many to compare pairwise, that finally steps back on itself
+    let staircase = vec!["R1, L1"; 200000].join(", ");
+    let path = get_path(&format!("{}, L1, L1", staircase)).unwrap();
+    assert_eq!(Some(((199999,199999), 399998.0)), calculate_first_intersect(&path, Metric::Manhattan));
+
+    let spiral = (1..=300).flat_map(|k| vec![format!("L{}", k); 2]).collect::<Vec<String>>().join(", ");
+    let path = get_path(&format!("{}, R0, R5", spiral)).unwrap();
+    assert_eq!(Some(((150,149), 299.0)), calculate_first_intersect(&path, Metric::Manhattan));
+
+    assert_eq!(Err(ParseError::new(1, 19, "2000000000", "a distance that keeps the walk in range")), get_path("R2000000000, R0, L2000000000").map(|_| ()));
+}
+
+#[test]
+fn crossings() {
+    // Doubling back along the first segment retraces a run of it rather than crossing at a point
+    let path = get_path("R5, R0, R2, R4, R1, R3").unwrap();
+    assert_eq!(vec![(0,0), (5,0), (5,0), (3,0), (3,4), (4,4), (4,1)], path.corners());
+    assert_eq!(Some((4,0)), path.first_crossing());
+    assert_eq!(vec![Segment { start: (4,0), end: (3,0) }], path.crossings());
+
+    let path = get_path("R8, R4, R4, R8").unwrap();
+    assert_eq!(vec![Segment { start: (4,0), end: (4,0) }], path.crossings());
+    assert_eq!(((0,-4), (8,4)), path.bounding_box());
+    assert_eq!(24, path.length());
+
+    let path = get_path("R3, L1, L1, L1, R2").unwrap();
+    assert_eq!(vec![Segment { start: (2,0), end: (2,0) }, Segment { start: (1,0), end: (0,0) }], path.crossings());
+    assert_eq!(None, get_path("L2, R7").unwrap().first_crossing());
+}
+
+#[test]
+fn extended_moves() {
+    // Turning around, then heading north-east regardless of which way the walk was facing
+    let path = get_path("R4, B1, NE2, L1, S3").unwrap();
+    assert_eq!(vec![(0,0), (4,0), (3,0), (5,2), (4,3), (4,0)], path.corners());
+    assert_eq!(Some((3,0)), path.first_crossing());
+    assert_eq!(vec![Segment { start: (3,0), end: (3,0) }, Segment { start: (4,1), end: (4,0) }], path.crossings());
+    assert_eq!(11, path.length());
+
+    // Diagonals that cross between points never share a step
+    assert_eq!(None, get_path("NE2, W1, SE1").unwrap().first_crossing());
+    assert_eq!(Some((1,1)), get_path("NE2, W2, SE1").unwrap().first_crossing());
+
+    assert_eq!(Err(ParseError::new(1, 5, "NN", "a move such as R, L, B, N or NE")), get_path("E5, NN5").map(|_| ()));
+}
+
+#[test]
+fn metrics() {
+    let path = get_path("E3, N4").unwrap();
+    assert_eq!(((3,4), 7.0), calculate_final_distance(&path, Metric::Manhattan));
+    assert_eq!(((3,4), 4.0), calculate_final_distance(&path, Metric::Chebyshev));
+    assert_eq!(((3,4), 5.0), calculate_final_distance(&path, Metric::Euclidean));
+
+    let input = "R2, L3, L1, L1, L1";
+    assert_eq!(Ok(Answer::Int(4)), Day1.part_one(input, &Params::new()));
+    assert_eq!(Ok(Answer::Int(2)), Day1.part_one(input, &Params::new().with("metric", "chebyshev")));
+    assert_eq!(Ok(Answer::Text("2.828".to_string())), Day1.part_two(input, &Params::new().with("metric", "euclidean")));
+    assert!(Day1.part_one(input, &Params::new().with("metric", "taxicab")).is_err());
+}
+
+#[test]
+fn pictured_path() {
+    let picture = Day1.picture("R2, L3", &Params::new()).unwrap().unwrap();
+    assert_eq!(vec![(0,0), (2,0), (2,-3)], picture.path);
+    assert_eq!(None, picture.grid);
+}
+
+#[test]
+fn differential_crossings() {
+    use aoc_core::differential::{shrink_items, Differential};
+
+    // Walking every step and remembering where it's been finds the same first crossing as comparing
+    // segments, diagonals included
+    Differential::new(|rng| {
+        Day1.generate(rng, 40).split(", ").map(|turn| match rng.one_in(3) {
+            true => format!("{}{}", rng.pick(MOVES).0, rng.range(0, 12)),
+            false => turn.to_string(),
+        }).collect::<Vec<String>>()
+    })
+        .shrink_with(|turns| shrink_items(turns).into_iter().filter(|t| !t.is_empty()).collect())
+        .implementation("segments", |turns| get_path(&turns.join(", ")).unwrap().first_crossing_by_segments())
+        .implementation("steps", |turns| get_path(&turns.join(", ")).unwrap().first_crossing_by_steps())
+        .check(100);
+}
@@ -0,0 +1,462 @@
+extern crate aoc_core;
+
+use aoc_core::parse::ParseError;
+use aoc_core::pattern::{FromCapture, Pattern};
+use aoc_core::params::Params;
+use aoc_core::random::Rng;
+use aoc_core::solution::{Answer, Solution};
+
+#[derive(Debug, PartialEq)]
+enum BotOutputType {
+	Bot {bot_number: i32},
+	Bin {bin_number: i32}
+}
+
+#[derive(Debug, PartialEq)]
+struct Input {
+	value: i32,
+	to_bot: i32
+}
+
+#[derive(Debug, PartialEq)]
+struct OutputRule {
+	low: BotOutputType,
+	high: BotOutputType
+}
+
+#[derive(Debug, PartialEq)]
+struct Bot {
+    bot_number: i32,
+    outputs: OutputRule,
+}
+
+#[derive(Debug, PartialEq)]
+struct Rules {
+    input_rules: Vec<Input>,
+    bot_rules: Vec<Bot>
+}
+
+#[derive(Debug, PartialEq)]
+pub struct State {
+    bins: Vec<i32>,
+    bots: Vec<(i32,i32)>
+}
+
+// None when the rules never mention one of the bins
+pub fn get_output_product(final_state: &State, bins: &[usize]) -> Option<i32> {
+	bins.iter().map(|bin| final_state.bins.get(*bin).copied()).product()
+}
+
+// None when no bot ever holds the pair
+pub fn find_bot_number(completed_output: &[(i32,i32)], microchip_pair: (i32,i32)) -> Option<i32> {
+	completed_output.iter().position(|&x| {
+		(x.0 == microchip_pair.0 || x.0 == microchip_pair.1) &&
+		(x.1 == microchip_pair.0 || x.1 == microchip_pair.1)
+	}).map(|bot| bot as i32)
+}
+
+fn get_num_bins(rules: &Rules) -> i32 {
+	rules.bot_rules.iter().fold(0, |last_max, bot| {
+		let low_bin_number = match bot.outputs.low {
+		    BotOutputType::Bin{bin_number} => bin_number,
+		    _ => 0
+		};
+
+		let high_bin_number = match bot.outputs.high {
+			BotOutputType::Bin{bin_number} => bin_number,
+			_ => 0
+		};
+
+		std::cmp::max(last_max, std::cmp::max(low_bin_number, high_bin_number))
+	})
+}
+
+fn get_num_bots(rules: &Rules) -> i32 {
+	let max_input_rules_bot = rules.input_rules.iter().map(|input| input.to_bot).max().unwrap_or(0);
+	let max_bot_rules_bot = rules.bot_rules.iter().fold(0, |last_max, bot| {
+		let low_bot_number = match bot.outputs.low {
+		    BotOutputType::Bot{bot_number} => bot_number,
+		    _ => 0
+		};
+
+		let high_bot_number = match bot.outputs.high {
+			BotOutputType::Bot{bot_number} => bot_number,
+			_ => 0
+		};
+
+		std::cmp::max(last_max, std::cmp::max(bot.bot_number, std::cmp::max(low_bot_number, high_bot_number)))
+	});
+
+	std::cmp::max(max_bot_rules_bot, max_input_rules_bot)
+}
+
+fn can_run_rule(token_pair: &(i32,i32)) -> bool {
+	token_pair.0 > -1 && token_pair.1 > -1
+}
+
+fn bots_have_work_to_do(current_state: &State) -> bool {
+	current_state.bots.iter().any(can_run_rule)
+}
+
+fn take_token_from_bot(token: i32, from_bot: i32, current_bots: &[(i32,i32)]) -> Vec<(i32,i32)> {
+	current_bots.iter().enumerate().map(|(i,bot)| {
+			if from_bot == i as i32 {
+				if bot.0 == token {
+					(-1, bot.1)
+				}
+				else {
+				    (bot.0, -1)
+				}
+			}
+			else {
+			    *bot
+			}
+	}).collect()
+}
+
+fn deposit_token_in_bin(token: i32, from_bot: i32, to_bin: i32, current_state: &State) -> State {
+	State {
+		//put the token in the bin
+		bins: current_state.bins.iter().enumerate().map(|(i,bin)| {
+			if to_bin == i as i32 {
+				token
+			}
+			else {
+			    *bin
+			}
+		}).collect(),
+		//take the token off the bot
+		bots: take_token_from_bot(token, from_bot, &current_state.bots)
+	}
+}
+
+fn give_token_to_bot(token: i32, from_bot: i32, to_bot: i32, current_state: &State) -> State {
+
+	let updated_bots: Vec<(i32,i32)> = current_state.bots.iter().enumerate().map(|(i,bot)| {
+			if to_bot == i as i32 {
+				if bot.0 == -1 {
+					(token, bot.1)
+				}
+				else {
+				    (bot.0, token)
+				}
+			}
+			else {
+			    *bot
+			}
+		}).collect();
+
+	State {
+		//give the token to the new bot
+		bins: current_state.bins.to_vec(),
+		//take if from the old bot
+		bots: take_token_from_bot(token, from_bot, &updated_bots)
+	}
+}
+
+pub fn run(input: &str) -> Result<(State, Vec<(i32,i32)>), ParseError> {
+	let rules = build_rules(input)?;
+
+	let max_bot_number = get_num_bots(&rules) + 1;
+	let max_bin_number = get_num_bins(&rules) + 1;
+
+	let mut state = rules.input_rules.iter().fold(State{ bots: vec!((-1,-1); max_bot_number as usize), 
+																bins: vec!(0; max_bin_number as usize) }, 
+																|current_state, rule| {
+		give_token_to_bot(rule.value, -1, rule.to_bot, &current_state)
+	});
+
+	let mut bot_compares = vec!((-1,-1); max_bot_number as usize);
+
+	loop {
+		let before = state.bots.clone();
+	    state = rules.bot_rules.iter().fold(state, |current_state, rule| {
+			let from_bot_number = rule.bot_number;
+			let bot = current_state.bots[from_bot_number as usize];
+
+			if can_run_rule(&bot) {
+				bot_compares[from_bot_number as usize] = (bot.0, bot.1);
+
+				let (low, high) = (std::cmp::min(bot.0, bot.1), std::cmp::max(bot.0, bot.1));
+
+				let low_rule = &rule.outputs.low;
+				let high_rule = &rule.outputs.high;
+
+			    let post_low_rule_state = match *low_rule {
+			        BotOutputType::Bot{bot_number} => give_token_to_bot(low, from_bot_number, bot_number, &current_state),
+			        BotOutputType::Bin{bin_number} => deposit_token_in_bin(low, from_bot_number, bin_number, &current_state)
+			    };
+			    
+			    match *high_rule {
+			        BotOutputType::Bot{bot_number} => give_token_to_bot(high, from_bot_number, bot_number, &post_low_rule_state),
+			        BotOutputType::Bin{bin_number} => deposit_token_in_bin(high, from_bot_number, bin_number, &post_low_rule_state)
+			    }
+			}
+			else {
+			    current_state
+			}
+		});
+
+	    //short-circuit if bots can't hand out anymore tokens, or the ones that could have no rule to follow
+		if !bots_have_work_to_do(&state) || state.bots == before {
+			break;
+		}
+	}
+
+	Ok((state, bot_compares))
+}
+
+// Whether a bot hands a chip to another bot or puts it in an output bin
+enum Receiver {
+	Bot,
+	Output
+}
+
+impl<'a> FromCapture<'a> for Receiver {
+	fn from_capture(line: &'a str, text: &'a str, expected: &str) -> Result<Receiver, ParseError> {
+		match text {
+			"bot" => Ok(Receiver::Bot),
+			"output" => Ok(Receiver::Output),
+			x => Err(ParseError::at(line, x, expected))
+		}
+	}
+}
+
+fn output_rule(receiver: Receiver, number: i32) -> BotOutputType {
+	match receiver {
+		Receiver::Bot => BotOutputType::Bot {bot_number: number},
+		Receiver::Output => BotOutputType::Bin {bin_number: number},
+	}
+}
+
+fn parse_input_rule(instruction: &str) -> Result<Option<Input>, ParseError> {
+	match instruction.split_whitespace().next() {
+	    Some("value") => {
+	    	let (value, to_bot): (u16, u16) = Pattern::new("value {a microchip value} goes to bot {a bot number}").parse(instruction)?;
+	    	Ok(Some(Input {value: value as i32, to_bot: to_bot as i32}))
+	    },
+	    _ => Ok(None),
+	}
+}
+
+fn parse_bot_rule(instruction: &str) -> Result<Option<Bot>, ParseError> {
+	let pattern = Pattern::new("bot {a bot number} gives low to {bot or output} {a bot or output number} and high to {bot or output} {a bot or output number}");
+
+	match instruction.split_whitespace().next() {
+	    Some("bot") => {
+	    	let (bot_number, low, low_number, high, high_number): (u16, _, u16, _, u16) = pattern.parse(instruction)?;
+	    	Ok(Some(Bot {
+	    		bot_number: bot_number as i32,
+	    		outputs: OutputRule {
+	    			low: output_rule(low, low_number as i32),
+	    			high: output_rule(high, high_number as i32)
+	    		}
+	    	}))
+	    },
+	    _ => Ok(None),
+	}
+}
+
+fn build_rules(instructions: &str) -> Result<Rules, ParseError> {
+	let mut rules = Rules { input_rules: Vec::new(), bot_rules: Vec::new() };
+
+	for (line_number, instruction) in instructions.lines().enumerate() {
+		let on_line = |error: ParseError| error.on_line(line_number + 1);
+		match (parse_input_rule(instruction).map_err(on_line)?, parse_bot_rule(instruction).map_err(on_line)?) {
+			(Some(input), _) => rules.input_rules.push(input),
+			(_, Some(bot)) => rules.bot_rules.push(bot),
+			_ => {
+				let first_word = instruction.split_whitespace().next().unwrap_or("");
+				return Err(on_line(ParseError::at(instruction, first_word, "value or bot")));
+			}
+		}
+	}
+
+	Ok(rules)
+}
+
+// A chip a bot has handed on but nobody has claimed yet: the giving bot and whether it's the high one
+type Handoff = (usize, bool);
+
+// `size` bots (at least two) wired so every bot ends up holding exactly two chips, plus sometimes one
+// stranded bot that only ever gets one. The first bot is handed the 61 and 17 chips from the default
+// params and sends them straight to outputs, so no other bot compares them. Returns the input, that
+// bot's number and the total value of all the chips.
+fn generate_bots(rng: &mut Rng, size: usize) -> (String, i32, i32) {
+	let bots = std::cmp::max(2, size);
+	let stranded = rng.one_in(3);
+	let mut numbers = (0..bots as i32 + stranded as i32).collect::<Vec<i32>>();
+	rng.shuffle(&mut numbers);
+	let mut values = (1..=200).filter(|&v| v != 61 && v != 17).collect::<Vec<i32>>();
+	rng.shuffle(&mut values);
+	let mut values = vec!(61, 17).into_iter().chain(values);
+
+	let mut lines = Vec::new();
+	let mut total = 0;
+	let mut pending: Vec<Handoff> = Vec::new();
+	let mut to_outputs: Vec<Handoff> = Vec::new();
+	let mut targets = vec!([String::new(), String::new()]; bots);
+
+	for (bot, &number) in numbers.iter().enumerate().take(bots) {
+		let mut giver = None;
+		for slot in 0..2 {
+			// The first bot gets 61 and 17, the second at least one fresh chip so there are three outputs
+			let can_take = bot > 1 || (bot == 1 && slot == 1);
+			let choices = pending.iter().enumerate().filter(|&(_, handoff)| Some(handoff.0) != giver).map(|(i, _)| i).collect::<Vec<usize>>();
+			if can_take && !choices.is_empty() && !rng.one_in(4) {
+				let (from, high) = pending.swap_remove(*rng.pick(&choices));
+				targets[from][high as usize] = format!("bot {}", number);
+				giver = Some(from);
+			} else {
+				let value = values.next().unwrap();
+				total += value;
+				lines.push(format!("value {} goes to bot {}", value, number));
+			}
+		}
+		match bot {
+			0 => to_outputs.extend([(bot, false), (bot, true)]),
+			_ => pending.extend([(bot, false), (bot, true)]),
+		}
+	}
+
+	to_outputs.extend(pending);
+	let mut outputs = (0..to_outputs.len()).collect::<Vec<usize>>();
+	rng.shuffle(&mut outputs);
+	for ((from, high), output) in to_outputs.into_iter().zip(outputs) {
+		targets[from][high as usize] = format!("output {}", output);
+	}
+
+	lines.extend(targets.iter().enumerate().map(|(bot, [low, high])| format!("bot {} gives low to {} and high to {}", numbers[bot], low, high)));
+	if stranded {
+		let (value, outputs) = (values.next().unwrap(), 2 * bots);
+		total += value;
+		lines.push(format!("value {} goes to bot {}", value, numbers[bots]));
+		lines.push(format!("bot {} gives low to output {} and high to output {}", numbers[bots], outputs, outputs + 1));
+	}
+	rng.shuffle(&mut lines);
+	(lines.join("\n"), numbers[0], total)
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+	fn part_one(&self, input: &str, params: &Params) -> Result<Answer, ParseError> {
+		let pair = params.get_pair("compare", (61,17))?;
+		// A configured pair is blamed where it was set, and the puzzle's own pair on the input
+		match find_bot_number(&run(input)?.1, pair) {
+			Some(bot) => Ok(bot.into()),
+			None if params.is_set("compare") => Err(params.reject("compare", "a pair of chips that some bot compares")),
+			None => Err(ParseError::at_end_of_input(input, &format!("a bot that compares chips {} and {}", pair.0, pair.1))),
+		}
+	}
+
+	fn part_two(&self, input: &str, _params: &Params) -> Result<Answer, ParseError> {
+		match get_output_product(&run(input)?.0, &[0, 1, 2]) {
+			Some(product) => Ok(product.into()),
+			None => Err(ParseError::at_end_of_input(input, "rules that fill outputs 0, 1 and 2")),
+		}
+	}
+
+	fn generate(&self, rng: &mut Rng, size: usize) -> String {
+		generate_bots(rng, size).0
+	}
+}
+
+#[test]
+fn instruction_test() {
+	assert_eq!(Input {value: 5, to_bot: 2}, parse_input_rule("value 5 goes to bot 2").unwrap().unwrap());
+	assert_eq!(Bot {bot_number: 2, outputs: OutputRule{low: BotOutputType::Bot{bot_number: 1}, high: BotOutputType::Bot{bot_number: 0}}}, parse_bot_rule("bot 2 gives low to bot 1 and high to bot 0").unwrap().unwrap());
+}
+
+#[test]
+fn bad_instructions() {
+	assert_eq!(Err(ParseError::new(1, 7, "two", "a microchip value")), parse_input_rule("value two goes to bot 2"));
+	assert_eq!(Err(ParseError::new(1, 20, "", "a bot number")), parse_input_rule("value 5 goes to bot"));
+	assert_eq!(Err(ParseError::new(1, 20, "basket", "bot or output")), parse_bot_rule("bot 2 gives low to basket 1 and high to bot 0"));
+	assert_eq!(Err(ParseError::new(1, 44, "", "a bot or output number")), parse_bot_rule("bot 2 gives low to bot 1 and high to output"));
+	assert_eq!(Err(ParseError::new(1, 13, "high", "\"low\"")), parse_bot_rule("bot 2 gives high to bot 1 and low to bot 0"));
+	assert_eq!(Err(ParseError::new(2, 1, "robot", "value or bot")), build_rules("value 5 goes to bot 2\nrobot 2 gives low to bot 1 and high to bot 0"));
+}
+
+#[test]
+fn construction_line_test() {
+	let input = "value 5 goes to bot 2
+bot 2 gives low to bot 1 and high to bot 0
+value 3 goes to bot 1
+bot 1 gives low to output 1 and high to bot 0
+bot 0 gives low to output 2 and high to output 0
+value 2 goes to bot 2";
+
+	let test_rules = Rules {
+		input_rules: vec!(
+					Input {value: 5, to_bot: 2},
+					Input {value: 3, to_bot: 1},
+					Input {value: 2, to_bot: 2}
+				),
+		bot_rules: vec!(
+				Bot {bot_number: 2, outputs: OutputRule{low: BotOutputType::Bot{bot_number: 1}, high: BotOutputType::Bot{bot_number: 0}}},
+				Bot {bot_number: 1, outputs: OutputRule{low: BotOutputType::Bin{bin_number: 1}, high: BotOutputType::Bot{bot_number: 0}}},
+				Bot {bot_number: 0, outputs: OutputRule{low: BotOutputType::Bin{bin_number: 2}, high: BotOutputType::Bin{bin_number: 0}}}
+			)
+	};
+
+	assert_eq!(Ok(test_rules), build_rules(input));
+}
+
+#[test]
+fn run_bots_test() {
+	let input = "value 5 goes to bot 2
+bot 2 gives low to bot 1 and high to bot 0
+value 3 goes to bot 1
+bot 1 gives low to output 1 and high to bot 0
+bot 0 gives low to output 2 and high to output 0
+value 2 goes to bot 2";
+
+	assert_eq!(State {bins: vec!(5, 2, 3), bots: vec!((-1,-1),(-1,-1),(-1,-1))}, run(input).unwrap().0);
+}
+
+#[test]
+fn bot_number_test() {
+	let input = "value 5 goes to bot 2
+bot 2 gives low to bot 1 and high to bot 0
+value 3 goes to bot 1
+bot 1 gives low to output 1 and high to bot 0
+bot 0 gives low to output 2 and high to output 0
+value 2 goes to bot 2";
+
+	assert_eq!(Some(2), find_bot_number(&run(input).unwrap().1, (5,2)));
+	assert_eq!(None, find_bot_number(&run(input).unwrap().1, (61,17)));
+}
+
+#[test]
+fn incomplete_rules() {
+	assert_eq!(Err(ParseError::new(1, 1, "", "a bot that compares chips 61 and 17")), Day10.part_one("", &Params::new()));
+	assert_eq!(Err(ParseError::new(1, 1, "", "rules that fill outputs 0, 1 and 2")), Day10.part_two("", &Params::new()));
+	let params = Params::new().with("compare", "5,3");
+	assert_eq!(Err(ParseError::new(1, 11, "5,3", "a pair of chips that some bot compares for compare")), Day10.part_one("value 5 goes to bot 0", &params));
+
+	// Only two outputs, and a bot with a rule but no chips numbered past every other bot
+	let input = "value 5 goes to bot 0\nvalue 2 goes to bot 0\nbot 0 gives low to output 1 and high to output 0\nbot 7 gives low to bot 0 and high to bot 0";
+	assert_eq!(Some(10), get_output_product(&run(input).unwrap().0, &[0, 1]));
+	assert_eq!(Err(ParseError::new(4, 43, "", "rules that fill outputs 0, 1 and 2")), Day10.part_two(input, &Params::new()));
+
+	// A bot holding two chips with no rule for what to do with them
+	assert_eq!(None, find_bot_number(&run("value 5 goes to bot 0\nvalue 2 goes to bot 0").unwrap().1, (5,2)));
+	assert_eq!(Err(ParseError::new(1, 21, "-1", "a bot number")), parse_input_rule("value 5 goes to bot -1"));
+}
+
+#[test]
+fn generated_bots() {
+	for seed in 0..20 {
+		let (input, compare_bot, total) = generate_bots(&mut Rng::new(seed), 30);
+		let (state, compares) = run(&input).unwrap();
+
+		assert_eq!(Some(compare_bot), find_bot_number(&compares, (61, 17)));
+		assert_eq!(1, compares.iter().filter(|&&(a, b)| (a, b) == (61, 17) || (a, b) == (17, 61)).count());
+
+		// Every chip ends up in an output except the stranded bot's, if there is one
+		let held = state.bots.iter().filter(|&&bot| bot != (-1, -1)).collect::<Vec<&(i32, i32)>>();
+		assert!(held.len() <= 1 && held.iter().all(|bot| bot.1 == -1));
+		assert_eq!(total, state.bins.iter().sum::<i32>() + held.iter().map(|bot| bot.0).sum::<i32>());
+	}
+}
@@ -0,0 +1,810 @@
+extern crate aoc_core;
+
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use aoc_core::debug;
+use aoc_core::parse::ParseError;
+use aoc_core::search::{Path, bidirectional};
+use aoc_core::params::Params;
+use aoc_core::random::Rng;
+use aoc_core::solution::{Answer, Solution};
+
+// An element name and the floor its generator or microchip sits on
+type Component = (String, usize);
+
+#[derive(Clone)]
+struct ContainmentAreaState {
+    top_floor: usize,
+    elevator: usize,
+    components: Vec<usize>,
+    elements: Vec<String>
+}
+
+impl Eq for ContainmentAreaState {}
+
+// Which element is which never matters to the moves left, only where each pair's parts are. Treating
+// states that differ only by swapping elements as the same keeps the search space small enough.
+impl PartialEq for ContainmentAreaState {
+    fn eq(&self, other: &ContainmentAreaState) -> bool {
+        self.elevator == other.elevator && self.pairs() == other.pairs()
+    }
+}
+
+impl Hash for ContainmentAreaState {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.elevator.hash(state);
+        self.pairs().hash(state);
+    }
+}
+
+impl fmt::Debug for ContainmentAreaState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.output())
+    }
+}
+
+impl ContainmentAreaState {
+    // The floors of each element's microchip and generator, in order
+    fn pairs(&self) -> Vec<(usize, usize)> {
+        let mut pairs = self.components.chunks(2).map(|pair| (pair[0], pair[1])).collect::<Vec<(usize, usize)>>();
+        pairs.sort_unstable();
+        pairs
+    }
+
+    // The word in front of each mention of a component kind on a floor, e.g. "hydrogen" in "a hydrogen generator"
+    fn parse_component_names<'a>(line: &'a str, kind: &str) -> Result<Vec<&'a str>, ParseError> {
+        line.rmatch_indices(kind).map(|(i, _)| {
+            line[0..i].split_whitespace().last().ok_or_else(|| ParseError::at(line, &line[i..i + kind.len()], "an element name"))
+        }).collect()
+    }
+
+    fn parse(input: &str) -> Result<ContainmentAreaState, ParseError> {
+        if input.lines().count() == 0 {
+            return Err(ParseError::at_end_of_input(input, "a floor"));
+        }
+
+        let mut generators: Vec<Component> = vec!();
+        let mut microchips = vec!();
+
+        for (floor_index, line) in input.lines().enumerate() {
+            if line.contains("nothing relevant") {
+                continue;
+            }
+
+            let on_floor = |error: ParseError| error.on_line(floor_index + 1);
+            generators.extend(ContainmentAreaState::parse_component_names(line, "generator").map_err(on_floor)?.into_iter().map(|name| (name.to_string(), floor_index)));
+            microchips.extend(ContainmentAreaState::parse_component_names(line, "microchip").map_err(on_floor)?.into_iter().map(|name| (line, name, floor_index)));
+        }
+
+        let mut components = vec!();
+        let mut elements = vec!();
+        for (line, microchip_name, microchip_floor) in microchips {
+            let element_name = microchip_name.split('-').next().unwrap();
+            let generator_floor = generators.iter().find(|&(gen_name, _)| gen_name == element_name)
+                .ok_or_else(|| ParseError::at(line, microchip_name, "a microchip with a matching generator").on_line(microchip_floor + 1))?.1;
+
+            components.extend([microchip_floor, generator_floor]);
+            elements.push(element_name.to_string());
+        }
+
+        Ok(ContainmentAreaState {
+            elevator: 0,
+            components,
+            elements,
+            top_floor: input.lines().count()
+        })
+    }
+
+    fn is_microchip(&self, component_index: usize) -> bool {
+        component_index.is_multiple_of(2)
+    }
+
+    fn get_component_name(&self, component_index: usize) -> String {
+        let is_microchip = self.is_microchip(component_index);
+        let element_index = if is_microchip { component_index / 2 } else {(component_index - 1)/2};
+        let element_name = self.elements[element_index].chars().take(2).collect::<String>().to_uppercase();
+        let type_name = if is_microchip {"m"} else {"g"};
+
+        format!("{}{}", element_name, type_name).to_string()
+    }
+
+    fn output(&self) -> String {
+        (0..self.top_floor).rev().fold("".to_string(), |out_str, floor_index| {
+            let components_str = self.components.iter().enumerate().map(|(i, comp)| {
+                if *comp == floor_index {
+                    self.get_component_name(i).to_string()
+                } else {
+                    " . ".to_string()
+                }
+            }).collect::<Vec<String>>().join("  ");
+
+            out_str + &format!("F{} {} {}\n", floor_index+1, if self.elevator == floor_index {"E  "} else {".  "}, components_str)
+        })
+    }
+
+    fn state_is_valid(&self) -> bool {
+        self.components.iter().enumerate().all(|(i,&floor)| {
+             !self.is_microchip(i) ||
+                //I'm a microchip and i'm on the same floor as my generator
+                self.components[i+1] == floor ||
+                //I'm a microchip and the rest of my floor is empty or microchips
+                self.components.iter().enumerate().all(|(j,&inner_floor)| (i == j || floor != inner_floor) || self.is_microchip(j)) 
+        })
+    }
+
+    fn permute_components(&self, component_indices: Vec<usize>, direction: i32) -> ContainmentAreaState {
+        ContainmentAreaState {
+            top_floor: self.top_floor,
+            elevator: (self.elevator as i32 + direction) as usize,
+            components: self.components.iter().enumerate().map(|(i,c)| {
+                if component_indices.contains(&i) {
+                    (*c as i32 + direction) as usize
+                } else {
+                    *c
+                }
+            }).collect(),
+            elements: self.elements.clone()
+        }
+    }
+
+    // Moving one or two of the components on the elevator's floor a floor up or down, without frying any microchips
+    fn generate_valid_children(&self) -> Vec<ContainmentAreaState> {
+        let on_floor = (0..self.components.len()).filter(|&i| self.components[i] == self.elevator).collect::<Vec<usize>>();
+        let directions = [1, -1].into_iter().filter(|&direction| {
+            (direction == 1 && self.elevator < self.top_floor - 1) || (direction == -1 && self.elevator > 0)
+        }).collect::<Vec<i32>>();
+
+        let mut children = vec!();
+        for (n, &i) in on_floor.iter().enumerate() {
+            for &direction in &directions {
+                children.push(self.permute_components(vec!(i), direction));
+                for &j in &on_floor[n + 1..] {
+                    children.push(self.permute_components(vec!(i, j), direction));
+                }
+            }
+        }
+
+        children.retain(|child| child.state_is_valid());
+        children
+    }
+
+    fn with_extra_elements(&self, element_names: &[&str], floor: usize) -> ContainmentAreaState {
+        ContainmentAreaState {
+            top_floor: self.top_floor,
+            elevator: self.elevator,
+            components: self.components.iter().cloned().chain(element_names.iter().flat_map(|_| vec!(floor, floor))).collect(),
+            elements: self.elements.iter().cloned().chain(element_names.iter().map(|name| name.to_string())).collect()
+        }
+    }
+
+    fn get_completed_version(&self) -> ContainmentAreaState {
+        ContainmentAreaState {
+            elevator: self.top_floor - 1,
+            components: self.components.iter().map(|_| self.top_floor - 1).collect(),
+            elements: self.elements.clone(),
+            top_floor: self.top_floor
+        }
+    }
+}
+
+pub fn find_shortest_path(input: &str) -> Result<usize, ParseError> {
+    find_shortest_path_from(input, ContainmentAreaState::parse(input)?)
+}
+
+// The floors after each step of a shortest route, from the input's starting point to the top
+pub fn floor_plans(input: &str) -> Result<Vec<String>, ParseError> {
+    let route = shortest_route(ContainmentAreaState::parse(input)?).ok_or_else(|| unsolvable(input))?;
+    Ok(route.states.iter().map(|state| state.output()).collect())
+}
+
+// Part two finds more elements on the first floor than the input lists
+pub fn find_shortest_path_with_extra_elements(input: &str, element_names: &[&str]) -> Result<usize, ParseError> {
+    find_shortest_path_from(input, ContainmentAreaState::parse(input)?.with_extra_elements(element_names, 0))
+}
+
+// Every move can be undone, so the search can work back from the finished state at the same time
+fn shortest_route(start: ContainmentAreaState) -> Option<Path<ContainmentAreaState>> {
+    let goal = start.get_completed_version();
+    let route = bidirectional(start, goal, |state| state.generate_valid_children())?;
+    debug!("Found a route of {} steps", route.cost);
+
+    // States with their elements swapped count as the same, so the half of the route found from the
+    // goal can have its elements in a different order. Replaying it from the start keeps every step a real move.
+    let mut states = vec![route.states[0].clone()];
+    for next in &route.states[1..] {
+        let step = states.last().unwrap().generate_valid_children().into_iter().find(|child| child == next).unwrap();
+        states.push(step);
+    }
+    Some(Path { states, cost: route.cost })
+}
+
+// A layout can be stuck, say with the elevator on an empty floor, and then the whole input is to blame
+fn unsolvable(input: &str) -> ParseError {
+    let first_floor = input.lines().next().unwrap_or("");
+    ParseError::at(first_floor, first_floor, "a layout that can all be brought up to the top floor")
+}
+
+fn find_shortest_path_from(input: &str, start: ContainmentAreaState) -> Result<usize, ParseError> {
+    shortest_route(start).map(|route| route.cost).ok_or_else(|| unsolvable(input))
+}
+
+const ELEMENTS: [&str; 10] = ["hydrogen", "lithium", "thulium", "plutonium", "strontium", "promethium", "ruthenium", "curium", "cobalt", "polonium"];
+const FLOORS: [&str; 4] = ["first", "second", "third", "fourth"];
+
+fn describe_floor(floor: usize, items: &[String]) -> String {
+    let contents = match items.len() {
+        0 => "nothing relevant".to_string(),
+        1 => items[0].clone(),
+        2 => format!("{} and {}", items[0], items[1]),
+        n => format!("{}, and {}", items[..n - 1].join(", "), items[n - 1]),
+    };
+    format!("The {} floor contains {}.", FLOORS[floor], contents)
+}
+
+// `size` elements (at most ten), placed by walking random moves down from the finished state until the
+// elevator reaches the first floor. Moves can be undone, so the walk backwards is a solution and every
+// input is solvable. Returns the input and the length of that walk.
+fn generate_facility(rng: &mut Rng, size: usize) -> (String, usize) {
+    let mut names = ELEMENTS.to_vec();
+    rng.shuffle(&mut names);
+    let elements = names[..size.clamp(1, ELEMENTS.len())].iter().map(|name| name.to_string()).collect::<Vec<String>>();
+
+    let top_floor = FLOORS.len();
+    let mut state = ContainmentAreaState {
+        top_floor,
+        elevator: top_floor - 1,
+        components: vec!(top_floor - 1; elements.len() * 2),
+        elements
+    };
+
+    let min_moves = rng.range(1, 30) as usize;
+    let mut moves = 0;
+    while moves < min_moves || state.elevator > 0 {
+        let children = state.generate_valid_children();
+        let downwards = children.iter().filter(|child| child.elevator < state.elevator).cloned().collect::<Vec<ContainmentAreaState>>();
+        state = match !downwards.is_empty() && rng.one_in(2) {
+            true => rng.pick(&downwards).clone(),
+            false => rng.pick(&children).clone(),
+        };
+        moves += 1;
+    }
+
+    let floors = (0..top_floor).map(|floor| {
+        let mut items = state.components.iter().enumerate().filter(|&(_, &f)| f == floor).map(|(i, _)| {
+            match state.is_microchip(i) {
+                true => format!("a {}-compatible microchip", state.elements[i / 2]),
+                false => format!("a {} generator", state.elements[i / 2]),
+            }
+        }).collect::<Vec<String>>();
+        rng.shuffle(&mut items);
+        describe_floor(floor, &items)
+    }).collect::<Vec<String>>();
+
+    (floors.join("\n"), moves)
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    fn part_one(&self, input: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(find_shortest_path(input)?.into())
+    }
+
+    fn part_two(&self, input: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(find_shortest_path_with_extra_elements(input, &["elerium", "dilithium"])?.into())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate_facility(rng, size).0
+    }
+}
+
+#[test]
+fn parse() {
+    let input = "The first floor contains a hydrogen-compatible microchip and a lithium-compatible microchip.
+The second floor contains a hydrogen generator.
+The third floor contains a lithium generator.
+The fourth floor contains nothing relevant.";
+
+    let expected = ContainmentAreaState {
+        top_floor: 4,
+        elevator: 0,
+        components: vec!(
+            0,
+            2,
+            0,
+            1
+        ),
+        elements: vec!(
+            "lithium".to_string(),
+            "hydrogen".to_string()
+        )
+    };
+
+    assert_eq!(expected, ContainmentAreaState::parse(input).unwrap());
+}
+
+#[test]
+fn unmatched_microchip() {
+    let input = "The first floor contains a hydrogen-compatible microchip.
+The second floor contains a lithium generator.";
+
+    let error = ContainmentAreaState::parse(input).unwrap_err();
+    assert_eq!(ParseError::new(1, 28, "hydrogen-compatible", "a microchip with a matching generator"), error);
+}
+
+#[test]
+fn path_gen() {
+    let input = "The first floor contains a hydrogen-compatible microchip and a lithium-compatible microchip.
+The second floor contains a hydrogen generator.
+The third floor contains a lithium generator.
+The fourth floor contains nothing relevant.";
+
+    assert_eq!(Ok(11), find_shortest_path(input));
+}
+
+#[test]
+fn child_generation_step_1() {
+    let state = ContainmentAreaState {
+        top_floor: 4,
+        elevator: 0,
+        components: vec!(
+            0,
+            2,
+            0,
+            1
+        ),
+        elements: vec!(
+            "lithium".to_string(),
+            "hydrogen".to_string()
+        )
+    };
+
+    let expected = ContainmentAreaState {
+        top_floor: 4,
+        elevator: 1,
+        components: vec!(
+            0,
+            2,
+            1,
+            1
+        ),
+        elements: vec!(
+            "lithium".to_string(),
+            "hydrogen".to_string()
+        )
+    };
+
+    assert!(state.generate_valid_children().contains(&expected));
+}
+
+#[test]
+fn child_generation_step_2() {
+    let state = ContainmentAreaState {
+        top_floor: 4,
+        elevator: 1,
+        components: vec!(
+            0,
+            2,
+            1,
+            1
+        ),
+        elements: vec!(
+            "lithium".to_string(),
+            "hydrogen".to_string()
+        )
+    };
+
+    let expected = ContainmentAreaState {
+        top_floor: 4,
+        elevator: 2,
+        components: vec!(
+            0,
+            2,
+            2,
+            2
+        ),
+        elements: vec!(
+            "lithium".to_string(),
+            "hydrogen".to_string()
+        )
+    };
+
+    assert!(state.generate_valid_children().contains(&expected));
+}
+
+#[test]
+fn child_generation_step_3() {
+    let state = ContainmentAreaState {
+        top_floor: 4,
+        elevator: 2,
+        components: vec!(
+            0,
+            2,
+            2,
+            2
+        ),
+        elements: vec!(
+            "lithium".to_string(),
+            "hydrogen".to_string()
+        )
+    };
+
+    let expected = ContainmentAreaState {
+        top_floor: 4,
+        elevator: 1,
+        components: vec!(
+            0,
+            2,
+            1,
+            2
+        ),
+        elements: vec!(
+            "lithium".to_string(),
+            "hydrogen".to_string()
+        )
+    };
+
+    assert!(state.generate_valid_children().contains(&expected));
+}
+
+#[test]
+fn child_generation_step_4() {
+    let state = ContainmentAreaState {
+        top_floor: 4,
+        elevator: 1,
+        components: vec!(
+            0,
+            2,
+            1,
+            2
+        ),
+        elements: vec!(
+            "lithium".to_string(),
+            "hydrogen".to_string()
+        )
+    };
+
+    let expected = ContainmentAreaState {
+        top_floor: 4,
+        elevator: 0,
+        components: vec!(
+            0,
+            2,
+            0,
+            2
+        ),
+        elements: vec!(
+            "lithium".to_string(),
+            "hydrogen".to_string()
+        )
+    };
+
+    assert!(state.generate_valid_children().contains(&expected));
+}
+
+#[test]
+fn child_generation_step_5() {
+    let state = ContainmentAreaState {
+        top_floor: 4,
+        elevator: 0,
+        components: vec!(
+            0,
+            2,
+            0,
+            2
+        ),
+        elements: vec!(
+            "lithium".to_string(),
+            "hydrogen".to_string()
+        )
+    };
+
+    let expected = ContainmentAreaState {
+        top_floor: 4,
+        elevator: 1,
+        components: vec!(
+            1,
+            2,
+            1,
+            2
+        ),
+        elements: vec!(
+            "lithium".to_string(),
+            "hydrogen".to_string()
+        )
+    };
+
+    assert!(state.generate_valid_children().contains(&expected));
+}
+
+#[test]
+fn child_generation_step_6() {
+    let state = ContainmentAreaState {
+        top_floor: 4,
+        elevator: 1,
+        components: vec!(
+            1,
+            2,
+            1,
+            2
+        ),
+        elements: vec!(
+            "lithium".to_string(),
+            "hydrogen".to_string()
+        )
+    };
+
+    let expected = ContainmentAreaState {
+        top_floor: 4,
+        elevator: 2,
+        components: vec!(
+            2,
+            2,
+            2,
+            2
+        ),
+        elements: vec!(
+            "lithium".to_string(),
+            "hydrogen".to_string()
+        )
+    };
+
+    assert!(state.generate_valid_children().contains(&expected));
+}
+
+#[test]
+fn child_generation_step_7() {
+    let state = ContainmentAreaState {
+        top_floor: 4,
+        elevator: 2,
+        components: vec!(
+            2,
+            2,
+            2,
+            2
+        ),
+        elements: vec!(
+            "lithium".to_string(),
+            "hydrogen".to_string()
+        )
+    };
+
+    let expected = ContainmentAreaState {
+        top_floor: 4,
+        elevator: 3,
+        components: vec!(
+            3,
+            2,
+            3,
+            2
+        ),
+        elements: vec!(
+            "lithium".to_string(),
+            "hydrogen".to_string()
+        )
+    };
+
+    assert!(state.generate_valid_children().contains(&expected));
+}
+
+#[test]
+fn child_generation_step_8() {
+    let state = ContainmentAreaState {
+        top_floor: 4,
+        elevator: 3,
+        components: vec!(
+            3,
+            2,
+            3,
+            2
+        ),
+        elements: vec!(
+            "lithium".to_string(),
+            "hydrogen".to_string()
+        )
+    };
+
+    let expected = ContainmentAreaState {
+        top_floor: 4,
+        elevator: 2,
+        components: vec!(
+            3,
+            2,
+            2,
+            2
+        ),
+        elements: vec!(
+            "lithium".to_string(),
+            "hydrogen".to_string()
+        )
+    };
+
+    assert!(state.generate_valid_children().contains(&expected));
+}
+
+#[test]
+fn child_generation_step_9() {
+    let state = ContainmentAreaState {
+        top_floor: 4,
+        elevator: 2,
+        components: vec!(
+            3,
+            2,
+            2,
+            2
+        ),
+        elements: vec!(
+            "lithium".to_string(),
+            "hydrogen".to_string()
+        )
+    };
+
+    let expected = ContainmentAreaState {
+        top_floor: 4,
+        elevator: 3 ,
+        components: vec!(
+            3,
+            3,
+            2,
+            3
+        ),
+        elements: vec!(
+            "lithium".to_string(),
+            "hydrogen".to_string()
+        )
+    };
+
+    assert!(state.generate_valid_children().contains(&expected));
+}
+
+#[test]
+fn child_generation_step_10() {
+    let state = ContainmentAreaState {
+        top_floor: 4,
+        elevator: 3 ,
+        components: vec!(
+            3,
+            3,
+            2,
+            3
+        ),
+        elements: vec!(
+            "lithium".to_string(),
+            "hydrogen".to_string()
+        )
+    };
+
+    let expected = ContainmentAreaState {
+        top_floor: 4,
+        elevator: 2,
+        components: vec!(
+            2,
+            3,
+            2,
+            3
+        ),
+        elements: vec!(
+            "lithium".to_string(),
+            "hydrogen".to_string()
+        )
+    };
+
+    assert!(state.generate_valid_children().contains(&expected));
+}
+
+#[test]
+fn child_generation_step_11() {
+    let state = ContainmentAreaState {
+        top_floor: 4,
+        elevator: 2,
+        components: vec!(
+            2,
+            3,
+            2,
+            3
+        ),
+        elements: vec!(
+            "lithium".to_string(),
+            "hydrogen".to_string()
+        )
+    };
+
+    let expected = ContainmentAreaState {
+        top_floor: 4,
+        elevator: 3,
+        components: vec!(
+            3,
+            3,
+            3,
+            3
+        ),
+        elements: vec!(
+            "lithium".to_string(),
+            "hydrogen".to_string()
+        )
+    };
+
+    assert!(state.generate_valid_children().contains(&expected));
+    assert!(expected == state.get_completed_version());
+}
+
+#[test]
+fn extra_elements() {
+    let input = "The first floor contains a hydrogen-compatible microchip and a lithium-compatible microchip.
+The second floor contains a hydrogen generator.
+The third floor contains a lithium generator.
+The fourth floor contains nothing relevant.";
+
+    let state = ContainmentAreaState::parse(input).unwrap().with_extra_elements(&["elerium"], 0);
+    assert_eq!(vec!(0, 2, 0, 1, 0, 0), state.components);
+    assert_eq!("elerium", state.elements[2]);
+}
+
+#[test]
+fn generated_facilities() {
+    for seed in 0..10 {
+        let (input, moves) = generate_facility(&mut Rng::new(seed), 2);
+        let state = ContainmentAreaState::parse(&input).unwrap();
+        assert_eq!((4, 4), (state.top_floor, state.components.len()));
+        assert!(state.state_is_valid());
+
+        // The walk that placed everything is one way back up, and every item has to ride the
+        // elevator at least as many floors as it starts below the top
+        let steps = find_shortest_path(&input).unwrap();
+        assert!(steps <= moves);
+        assert!(steps >= state.components.iter().map(|&floor| 3 - floor).max().unwrap());
+    }
+}
+
+#[test]
+fn floor_plan_drawings() {
+    let input = "The first floor contains a hydrogen-compatible microchip and a lithium-compatible microchip.
+The second floor contains a hydrogen generator.
+The third floor contains a lithium generator.
+The fourth floor contains nothing relevant.";
+
+    let plans = floor_plans(input).unwrap();
+    let (start, goal) = (&plans[0], &plans[plans.len() - 1]);
+    assert_eq!(12, plans.len());
+    assert_eq!("F4 .    .    .    .    . \nF3 .    .   LIg   .    . \nF2 .    .    .    .   HYg\nF1 E   LIm   .   HYm   . \n", start);
+    assert_eq!("F4 E   LIm  LIg  HYm  HYg\nF3 .    .    .    .    . \nF2 .    .    .    .    . \nF1 .    .    .    .    . \n", goal);
+}
+
+
+#[test]
+fn routes_are_real_moves() {
+    // Each step of the route moves the elevator one floor, taking one or two of its own components along
+    for seed in 0..10 {
+        let (input, _) = generate_facility(&mut Rng::new(seed), 5);
+        let route = shortest_route(ContainmentAreaState::parse(&input).unwrap()).unwrap();
+        for pair in route.states.windows(2) {
+            let (from, to) = (&pair[0], &pair[1]);
+            let moved = (0..from.components.len()).filter(|&i| from.components[i] != to.components[i]).collect::<Vec<usize>>();
+            assert_eq!(1, (from.elevator as i32 - to.elevator as i32).abs());
+            assert!((1..=2).contains(&moved.len()));
+            assert!(moved.iter().all(|&i| from.components[i] == from.elevator && to.components[i] == to.elevator));
+            assert!(to.state_is_valid());
+        }
+    }
+}
+
+#[test]
+fn stuck_layout() {
+    // The elevator starts on an empty floor, so nothing can ever move
+    let input = "The first floor contains nothing relevant.
+The second floor contains a hydrogen generator and a hydrogen-compatible microchip.";
+    let first_floor = "The first floor contains nothing relevant.";
+    assert_eq!(Err(ParseError::new(1, 1, first_floor, "a layout that can all be brought up to the top floor")), find_shortest_path(input));
+    assert!(floor_plans(input).is_err());
+}
+
+#[test]
+fn empty_facility() {
+    assert_eq!(Err(ParseError::new(1, 1, "", "a floor")), find_shortest_path(""));
+    assert_eq!(Err(ParseError::new(1, 1, "", "a floor")), floor_plans(""));
+
+    // Element names are drawn by their first two letters, or all of them if they're shorter
+    let input = "The first floor contains a x generator and a x-compatible microchip.";
+    assert_eq!(Ok(vec!["F1 E   Xm  Xg\n".to_string()]), floor_plans(input));
+}
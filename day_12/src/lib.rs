@@ -0,0 +1,361 @@
+extern crate aoc_core;
+
+use std::fmt;
+use std::io::Write;
+use aoc_core::parse::{ParseError, field, parse_lines};
+use aoc_core::pattern::{FromCapture, Pattern};
+use aoc_core::params::Params;
+use aoc_core::random::Rng;
+use aoc_core::render::Renderer;
+use aoc_core::solution::{Answer, Solution};
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum Val {
+    Raw{val: i32},
+    Reg{ptr: usize}
+}
+
+#[derive(Eq, PartialEq)]
+pub enum Instruction {
+    Copy{val: Val, ptr: usize},
+    Inc{ptr:usize},
+    Dec{ptr:usize},
+    Jump{val: Val, dist: i32}
+}
+
+impl fmt::Debug for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_str())
+    }
+}
+
+impl Instruction {
+    fn to_str(&self) -> String {
+        match *self {
+            Instruction::Copy{ref val, ptr} => {
+                format!("cpy {} {}", match *val {
+                    Val::Raw{val: inner_val} => inner_val.to_string(),
+                    Val::Reg{ptr: inner_ptr} => from_reg_index(inner_ptr),
+                }, from_reg_index(ptr))
+            },
+            Instruction::Inc{ptr} => {
+                format!("inc {}", from_reg_index(ptr))
+            },
+            Instruction::Dec{ptr} => {
+                format!("dec {}", from_reg_index(ptr))
+            },
+            Instruction::Jump{ref val, dist} => {
+                format!("jnz {} {}", match *val {
+                    Val::Raw{val: inner_val} => inner_val.to_string(),
+                    Val::Reg{ptr: inner_ptr} => from_reg_index(inner_ptr),
+                }, dist)
+            }
+        } 
+    }
+
+    fn run(&self, registers: &[i32]) -> (Vec<i32>, i32) {
+        match *self {
+            Instruction::Copy{ref val, ptr} => {
+                let copy_val = match *val {
+                    Val::Raw{val: inner_val} => inner_val,
+                    Val::Reg{ptr: inner_ptr} => registers[inner_ptr],
+                };
+
+                (registers.iter().enumerate().map(|(i, x)| if i == ptr {copy_val} else {*x}).collect(), 1)
+            },
+            Instruction::Inc{ptr} => {
+                (registers.iter().enumerate().map(|(i, x)| if i == ptr {x + 1} else {*x}).collect(), 1)
+            },
+            Instruction::Dec{ptr} => {
+                (registers.iter().enumerate().map(|(i, x)| if i == ptr {x - 1} else {*x}).collect(), 1)
+            },
+            Instruction::Jump{ref val, dist} => {
+                let should_jump = match *val {
+                    Val::Raw{val: inner_val} => inner_val != 0,
+                    Val::Reg{ptr: inner_ptr} => registers[inner_ptr] != 0,
+                };
+
+                (registers.to_vec(), if should_jump {dist} else {1})
+            }
+        }
+    }
+}
+
+fn from_reg_index(i: usize) -> String {
+    match i {
+        0 => "a".to_string(),
+        1 => "b".to_string(),
+        2 => "c".to_string(),
+        3 => "d".to_string(),
+        _ => panic!("Unknown reg index {}", i)
+    }
+}
+
+pub fn to_reg_index(reg: &str) -> usize {
+    match reg {
+        "a" => 0,
+        "b" => 1,
+        "c" => 2,
+        "d" => 3,
+        x   => panic!("Unknown register {}", x)
+    }
+}
+
+// A register operand, as its index
+struct Reg(usize);
+
+impl<'a> FromCapture<'a> for Reg {
+    fn from_capture(line: &'a str, text: &'a str, expected: &str) -> Result<Reg, ParseError> {
+        match text {
+            "a" | "b" | "c" | "d" => Ok(Reg(to_reg_index(text))),
+            x => Err(ParseError::at(line, x, expected))
+        }
+    }
+}
+
+impl<'a> FromCapture<'a> for Val {
+    fn from_capture(line: &'a str, text: &'a str, expected: &str) -> Result<Val, ParseError> {
+        match text.parse::<i32>() {
+            Ok(x) => Ok(Val::Raw{val: x}),
+            Err(_) => Ok(Val::Reg{ptr: Reg::from_capture(line, text, expected)?.0})
+        }
+    }
+}
+
+fn parse_instruction(line: &str) -> Result<Instruction, ParseError> {
+    let split_instr = line.split_whitespace().collect::<Vec<&str>>();
+    match field(line, &split_instr, 0, "an instruction")? {
+        "cpy" => {
+            let (val, Reg(ptr)) = Pattern::new("cpy {a register or a number} {a register a, b, c or d}").parse(line)?;
+            Ok(Instruction::Copy {val, ptr})
+        },
+        "inc" => {
+            let Reg(ptr) = Pattern::new("inc {a register a, b, c or d}").parse(line)?;
+            Ok(Instruction::Inc {ptr})
+        },
+        "dec" => {
+            let Reg(ptr) = Pattern::new("dec {a register a, b, c or d}").parse(line)?;
+            Ok(Instruction::Dec {ptr})
+        },
+        "jnz" => {
+            let (val, dist) = Pattern::new("jnz {a register or a number} {a jump distance}").parse(line)?;
+            Ok(Instruction::Jump {val, dist})
+        },
+        x => Err(ParseError::at(line, x, "cpy, inc, dec or jnz")),
+    }
+}
+
+pub fn parse_instructions(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    parse_lines(input, parse_instruction)
+}
+
+pub fn listing(instructions: &[Instruction], ptr: usize, registers: &[i32]) -> String {
+    let mut lines = Vec::new();
+
+    for (i, instr) in instructions.iter().enumerate() {
+        lines.push(format!("{} {:?}", if i == ptr {"->"} else {"  "}, instr));
+    }
+
+    lines.push(String::new());
+
+    for (i, reg) in registers.iter().enumerate() {
+        lines.push(format!("{} {}", from_reg_index(i), reg));
+    }
+
+    lines.join("\n")
+}
+
+pub fn run(instructions: &[Instruction]) -> Vec<i32> {
+    run_with_registers(instructions, vec!(0; 4))
+}
+
+pub fn run_with_registers(instructions: &[Instruction], initial_registers: Vec<i32>) -> Vec<i32> {
+    execute(instructions, initial_registers, |_, _| {})
+}
+
+// Redraws the listing and registers after every step
+pub fn run_visualised<W: Write>(instructions: &[Instruction], initial_registers: Vec<i32>, renderer: &mut Renderer<W>) -> Vec<i32> {
+    let mut last_ptr = 0;
+    let registers = execute(instructions, initial_registers, |ptr, registers| {
+        last_ptr = ptr;
+        renderer.frame(|| listing(instructions, ptr, registers));
+    });
+    renderer.finish(|| listing(instructions, last_ptr, &registers));
+    registers
+}
+
+fn execute<F: FnMut(usize, &[i32])>(instructions: &[Instruction], initial_registers: Vec<i32>, mut on_step: F) -> Vec<i32> {
+    let mut instruction_pointer = 0;
+    let mut registers = initial_registers;
+    let instruction_stack = instructions;
+
+    while instruction_pointer < instruction_stack.len() {
+        let (new_registers, next_instr) = instruction_stack[instruction_pointer].run(&registers);
+        on_step(instruction_pointer, &new_registers);
+        instruction_pointer = (instruction_pointer as i32 + next_instr) as usize;
+        registers = new_registers;
+    }
+
+    registers.to_vec()
+}
+
+// About `size` instructions that always halt: the only backward jumps close counted loops whose
+// counter register the loop body never touches
+fn generate_block(rng: &mut Rng, size: usize, free: &[usize]) -> Vec<String> {
+    let mut block = Vec::new();
+    while block.len() < size {
+        let reg = from_reg_index(*rng.pick(free));
+        let remaining = size - block.len();
+        match rng.below(8) {
+            0 => block.push(format!("cpy {} {}", rng.range(-5, 30), reg)),
+            1 => block.push(format!("cpy {} {}", from_reg_index(rng.below(4)), reg)),
+            2 | 3 => block.push(format!("inc {}", reg)),
+            4 => block.push(format!("dec {}", reg)),
+            5 if remaining > 2 => {
+                let skipped_size = rng.range(0, 3) as usize;
+                let skipped = generate_block(rng, skipped_size, free);
+                let condition = match rng.one_in(2) {
+                    true => rng.range(0, 1).to_string(),
+                    false => from_reg_index(rng.below(4)),
+                };
+                block.push(format!("jnz {} {}", condition, skipped.len() + 1));
+                block.extend(skipped);
+            },
+            _ if free.len() > 1 && remaining > 3 => {
+                let counter = *rng.pick(free);
+                let inner = free.iter().copied().filter(|&r| r != counter).collect::<Vec<usize>>();
+                let body_size = rng.range(1, std::cmp::min(remaining - 3, 6) as i64) as usize;
+                let body = generate_block(rng, body_size, &inner);
+                block.push(format!("cpy {} {}", rng.range(1, 5), from_reg_index(counter)));
+                block.extend(body.iter().cloned());
+                block.push(format!("dec {}", from_reg_index(counter)));
+                block.push(format!("jnz {} -{}", from_reg_index(counter), body.len() + 1));
+            },
+            _ => block.push(format!("inc {}", reg)),
+        }
+    }
+    block
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    fn part_one(&self, input: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(run(&parse_instructions(input)?)[to_reg_index("a")].into())
+    }
+
+    fn part_two(&self, input: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(run_with_registers(&parse_instructions(input)?, vec!(0, 0, 1, 0))[to_reg_index("a")].into())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate_block(rng, size, &[0, 1, 2, 3]).join("\n")
+    }
+}
+
+#[test]
+fn parse() {
+    let input = "cpy 41 a
+inc a
+inc a
+dec a
+jnz a 2
+dec a";
+
+    let expected = vec!(Instruction::Copy{val: Val::Raw{val: 41}, ptr: 0},
+                        Instruction::Inc{ptr: 0},
+                        Instruction::Inc{ptr: 0},
+                        Instruction::Dec{ptr: 0},
+                        Instruction::Jump{val: Val::Reg{ptr: 0}, dist: 2},
+                        Instruction::Dec{ptr: 0});
+
+    let output = parse_instructions(input).unwrap();
+
+    println!("{:#?}", expected);
+    println!("{:#?}", output);
+
+    assert_eq!(expected, output);
+}
+
+#[test]
+fn bad_instructions() {
+    assert_eq!(Err(ParseError::new(1, 1, "mov", "cpy, inc, dec or jnz")), parse_instructions("mov 41 a"));
+    assert_eq!(Err(ParseError::new(1, 8, "e", "a register a, b, c or d")), parse_instructions("cpy 41 e"));
+    assert_eq!(Err(ParseError::new(1, 5, "x", "a register or a number")), parse_instructions("jnz x 2"));
+    assert_eq!(Err(ParseError::new(2, 7, "two", "a jump distance")), parse_instructions("inc a\njnz a two"));
+    assert_eq!(Err(ParseError::new(1, 4, "", "a register a, b, c or d")), parse_instructions("inc"));
+    assert_eq!(Err(ParseError::new(1, 7, "b", "the end of the line")), parse_instructions("inc a b"));
+}
+
+#[test]
+fn run_test() {
+    let input = "cpy 41 a
+inc a
+inc a
+dec a
+jnz a 2
+dec a";
+
+    assert_eq!(42, run(&parse_instructions(input).unwrap())[to_reg_index("a")]);
+}
+
+#[test]
+fn cpy() {
+    let input = "cpy 41 a";
+    let registers = vec!(0; 4);
+
+    let instr = &parse_instructions(input).unwrap()[0];
+
+    assert_eq!((vec!(41,0,0,0), 1), instr.run(&registers));
+}
+
+#[test]
+fn inc() {
+    let input = "inc a";
+    let registers = vec!(41,0,0,0);
+
+    let instr = &parse_instructions(input).unwrap()[0];
+
+    assert_eq!((vec!(42,0,0,0), 1), instr.run(&registers));
+}
+
+#[test]
+fn dec() {
+    let input = "dec a";
+    let registers = vec!(42,0,0,0);
+
+    let instr = &parse_instructions(input).unwrap()[0];
+
+    assert_eq!((vec!(41,0,0,0), 1), instr.run(&registers));
+}
+
+#[test]
+fn jnz() {
+    let input = "jnz a 2";
+    let registers = vec!(41,0,0,0);
+
+    let instr = &parse_instructions(input).unwrap()[0];
+
+    assert_eq!((vec!(41,0,0,0), 2), instr.run(&registers));
+}
+
+#[test]
+fn generated_programs() {
+    // Printing the parsed program gives back exactly the generated text
+    for seed in 0..20 {
+        let input = Day12.generate(&mut Rng::new(seed), 30);
+        let printed = parse_instructions(&input).unwrap().iter().map(|instr| format!("{:?}", instr)).collect::<Vec<String>>().join("\n");
+        assert_eq!(input, printed);
+        run(&parse_instructions(&input).unwrap());
+    }
+}
+
+#[test]
+fn visualised_run() {
+    let instructions = parse_instructions("cpy 2 a\ndec a\njnz a -1").unwrap();
+    let mut renderer = Renderer::new(Vec::new(), false, 0);
+    assert_eq!(vec!(0, 0, 0, 0), run_visualised(&instructions, vec!(0; 4), &mut renderer));
+
+    let expected = "   cpy 2 a\n   dec a\n-> jnz a -1\n\na 0\nb 0\nc 0\nd 0\n";
+    assert_eq!(expected, String::from_utf8(renderer.into_inner()).unwrap());
+}
@@ -0,0 +1,254 @@
+extern crate aoc_core;
+
+use std::cmp;
+use std::fmt;
+use aoc_core::grid::{Grid, neighbours4};
+use aoc_core::parse::{ParseError, token};
+use aoc_core::search::{astar, bfs, reachable_within};
+use aoc_core::params::Params;
+use aoc_core::random::Rng;
+use aoc_core::solution::{Answer, Solution};
+use aoc_core::svg::Picture;
+
+#[derive(PartialEq, Eq)]
+enum LocationType {
+    Wall,
+    Open,
+}
+
+impl fmt::Debug for LocationType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+impl LocationType {
+    fn symbol(&self) -> char {
+        match *self {
+            LocationType::Wall => '#',
+            LocationType::Open => '.'
+        }
+    }
+
+    fn construct(input: u32) -> LocationType {
+        if input.is_multiple_of(2) {
+            LocationType::Open
+        } else {
+            LocationType::Wall
+        }
+    }
+}
+
+fn get_location_type((x,y): (u64,u64), designer_num: u64) -> LocationType {
+    LocationType::construct(((x*x + 3*x + 2*x*y + y + y*y) + designer_num).count_ones())
+}
+
+fn get_neighbours(node: (usize, usize), designer_num: u64) -> Vec<(usize, usize)> {
+    neighbours4((node.0 as i32, node.1 as i32))
+        .filter(|&(x, y)| x >= 0 && y >= 0)
+        .map(|(x, y)| (x as usize, y as usize))
+        .filter(|&(x, y)| get_location_type((x as u64, y as u64), designer_num) == LocationType::Open)
+        .collect()
+}
+
+// The part of the office closest to the origin, which otherwise goes on forever
+fn office(width: usize, height: usize, designer_num: u64) -> Grid<LocationType> {
+    Grid::from_fn(width, height, |(x, y)| get_location_type((x as u64, y as u64), designer_num))
+}
+
+pub fn find_reachable_nodes(from: (usize, usize), max_distance: usize, designer_num: u64) -> usize {
+    reachable_within(from, |&location| get_neighbours(location, designer_num), max_distance).len()
+}
+
+// Every step costs one and can't get any closer to the target than the manhattan distance. The office
+// goes on forever, so the search starts in a box around both squares and doubles it until the route
+// beats any that leaves the box, or until the start or the target is walled in short of its edges.
+pub fn find_shortest_route(from: (usize, usize), to: (usize, usize), designer_num: u64) -> Option<Vec<(usize,usize)>> {
+    let distance_left = |&(x, y): &(usize, usize)| x.abs_diff(to.0) + y.abs_diff(to.1);
+    let mut bound = 2 * [from.0, from.1, to.0, to.1].into_iter().max().unwrap() + 10;
+    loop {
+        let inside = |&location: &(usize, usize)| {
+            get_neighbours(location, designer_num).into_iter().filter(move |&(x, y)| x <= bound && y <= bound)
+        };
+        match astar(from, |location| inside(location).map(|neighbour| (neighbour, 1)), distance_left, |&location| location == to) {
+            Some(path) => {
+                // A route out of the box has to get to one past its edge and back again
+                let leaving = cmp::min(2 * (bound + 1) - from.0 - to.0, 2 * (bound + 1) - from.1 - to.1);
+                if path.cost <= leaving {
+                    return Some(path.states);
+                }
+            },
+            None => {
+                let walled_in = |end| reachable_within(end, inside, usize::MAX).into_keys().all(|(x, y)| x < bound && y < bound);
+                if walled_in(from) || walled_in(to) {
+                    return None;
+                }
+            }
+        }
+        bound *= 2;
+    }
+}
+
+fn is_open((x, y): (usize, usize), designer_num: u64) -> bool {
+    get_location_type((x as u64, y as u64), designer_num) == LocationType::Open
+}
+
+// The configured square both parts start from, which has to be open to go anywhere
+fn configured_start(params: &Params, designer_num: u64) -> Result<(usize, usize), ParseError> {
+    let start = params.get_pair("start", (1,1))?;
+    if !is_open(start, designer_num) {
+        return Err(params.reject("start", "an open square"));
+    }
+    Ok(start)
+}
+
+// Part one's route between the configured squares, blaming the parameters when there isn't one
+fn configured_route(params: &Params, designer_num: u64) -> Result<Vec<(usize, usize)>, ParseError> {
+    let (start, target) = (configured_start(params, designer_num)?, params.get_pair("target", (31,39))?);
+    if !is_open(target, designer_num) {
+        return Err(params.reject("target", "an open square"));
+    }
+    find_shortest_route(start, target, designer_num).ok_or_else(|| params.reject("target", "a square that can be reached from the start"))
+}
+
+pub fn parse_designer_number(input: &str) -> Result<u64, ParseError> {
+    let designer_num = input.trim();
+    match designer_num.split_whitespace().nth(1) {
+        Some(extra) => Err(ParseError::at(designer_num, extra, "a single designer number")),
+        None => token(designer_num, designer_num, "a designer number")
+    }
+}
+
+pub fn draw_room((max_x,max_y): (usize, usize), designer_num: u64, path: &[(usize, usize)]) -> String {
+    let mut room = office(max_x, max_y, designer_num).map(LocationType::symbol);
+    for &(x, y) in path {
+        if let Some(cell) = room.get_mut((x as i32, y as i32)) {
+            *cell = 'O';
+        }
+    }
+    room.render(|cell| *cell)
+}
+
+// Whether `to` can be reached from `from` without leaving the square up to `bound`. The office goes
+// on forever, so the route search never gives up on a target walled off from the start.
+fn is_reachable_within(from: (usize, usize), to: (usize, usize), designer_num: u64, bound: usize) -> bool {
+    let neighbours = |&location: &(usize, usize)| get_neighbours(location, designer_num).into_iter().filter(|&(x, y)| x <= bound && y <= bound);
+    bfs(from, neighbours, |&location| location == to).is_some()
+}
+
+// A designer number below `size` thousand whose maze joins the default start and target squares
+fn generate_designer_number(rng: &mut Rng, size: usize) -> u64 {
+    let largest = std::cmp::max(1, size as i64 * 1000);
+    (0..).map(|_| rng.range(1, largest) as u64)
+        .find(|&designer_num| get_location_type((1, 1), designer_num) == LocationType::Open && is_reachable_within((1,1), (31,39), designer_num, 80))
+        .unwrap()
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    fn part_one(&self, input: &str, params: &Params) -> Result<Answer, ParseError> {
+        Ok((configured_route(params, parse_designer_number(input)?)?.len() - 1).into())
+    }
+
+    fn part_two(&self, input: &str, params: &Params) -> Result<Answer, ParseError> {
+        let designer_num = parse_designer_number(input)?;
+        Ok(find_reachable_nodes(configured_start(params, designer_num)?, params.get("max_steps", 50)?, designer_num).into())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate_designer_number(rng, size).to_string()
+    }
+
+    // The part one route, with enough of the office around it to see the walls it goes past
+    fn picture(&self, input: &str, params: &Params) -> Result<Option<Picture>, ParseError> {
+        let designer_num = parse_designer_number(input)?;
+        let route = configured_route(params, designer_num)?;
+        let width = route.iter().map(|&(x, _)| x).max().unwrap_or(0) + 2;
+        let height = route.iter().map(|&(_, y)| y).max().unwrap_or(0) + 2;
+
+        let walls = office(width, height, designer_num).map(|location| *location == LocationType::Wall);
+        Ok(Some(Picture::route(walls, route.into_iter().map(|(x, y)| (x as i32, y as i32)).collect())))
+    }
+}
+
+#[test]
+fn test() {
+    let path = find_shortest_route((1,1), (7,4), 10).unwrap();
+    assert_eq!(11, path.len()-1);
+}
+
+#[test]
+fn drawn_room() {
+    let room = ".#.####.##
+..#..#...#
+#....##...
+###.#.###.
+.##..#..#.
+..##....#.
+#...##.###";
+    assert_eq!(room, draw_room((10, 7), 10, &[]));
+
+    let path = find_shortest_route((1,1), (7,4), 10).unwrap();
+    let drawn = draw_room((10, 7), 10, &path);
+    assert_eq!(12, drawn.matches('O').count());
+    assert_eq!(room.matches('#').count(), drawn.matches('#').count());
+}
+
+#[test]
+fn designer_numbers() {
+    assert_eq!(Ok(1364), parse_designer_number("1364\n"));
+    assert_eq!(Err(ParseError::new(1, 1, "", "a designer number")), parse_designer_number(""));
+    assert_eq!(Err(ParseError::new(1, 1, "13a", "a designer number")), parse_designer_number("13a"));
+    assert_eq!(Err(ParseError::new(1, 6, "10", "a single designer number")), parse_designer_number("1364 10"));
+}
+
+#[test]
+fn configured_maze() {
+    let params = Params::new().with("target", "7,4").with("max_steps", "2");
+    assert_eq!(Ok(Answer::Int(11)), Day13.part_one("10", &params));
+    assert_eq!(Ok(Answer::Int(5)), Day13.part_two("10", &params));
+
+    // (2,0) is open but boxed in by walls, and (1,0) is a wall
+    assert_eq!(None, find_shortest_route((1,1), (2,0), 10));
+    let walled_off = Params::new().with("start", "2,0").with("target", "7,4");
+    assert_eq!(Err(ParseError::new(1, 10, "7,4", "a square that can be reached from the start for target")), Day13.part_one("10", &walled_off));
+    let wall = Params::new().with("target", "1,0");
+    assert_eq!(Err(ParseError::new(1, 10, "1,0", "an open square for target")), Day13.part_one("10", &wall));
+    assert!(Day13.picture("10", &wall).is_err());
+    let wall_start = Params::new().with("start", "1,0");
+    assert_eq!(Err(ParseError::new(1, 9, "1,0", "an open square for start")), Day13.part_two("10", &wall_start));
+}
+
+#[test]
+fn long_way_round() {
+    // Every way from (1,1) to (8,1) for designer 19 goes out past (26,26) and back
+    let route = find_shortest_route((1,1), (8,1), 19).unwrap();
+    assert_eq!(101, route.len() - 1);
+    assert!(route.iter().any(|&(x, y)| x > 26 || y > 26));
+}
+
+#[test]
+fn generated_mazes() {
+    for seed in 0..10 {
+        let designer_num = parse_designer_number(&Day13.generate(&mut Rng::new(seed), 10)).unwrap();
+        let route = find_shortest_route((1,1), (31,39), designer_num).unwrap();
+
+        // The route runs from the start to the target, one open square at a time, and can't beat the
+        // straight-line distance
+        assert_eq!((Some(&(1,1)), Some(&(31,39))), (route.first(), route.last()));
+        assert!(route.windows(2).all(|step| get_neighbours(step[0], designer_num).contains(&step[1])));
+        assert!(route.len() > 30 + 38);
+        assert!(find_reachable_nodes((1,1), 50, designer_num) >= 1);
+    }
+}
+
+#[test]
+fn pictured_route() {
+    let params = Params::new().with("target", "7,4");
+    let picture = Day13.picture("10", &params).unwrap().unwrap();
+    let walls = picture.grid.unwrap();
+    assert_eq!((9, 7), (walls.width(), walls.height()));
+    assert_eq!(12, picture.path.len());
+    assert!(picture.path.iter().all(|&point| !walls[point]));
+}
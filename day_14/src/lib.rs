@@ -0,0 +1,243 @@
+extern crate aoc_core;
+
+use std::cmp;
+use aoc_core::parse::{ParseError, token};
+use aoc_core::pattern::Pattern;
+use aoc_core::params::Params;
+use aoc_core::random::Rng;
+use aoc_core::solution::{Answer, Solution};
+use aoc_core::trace;
+
+#[derive(Debug)]
+pub struct Reindeer {
+    speed: i32,
+    fly_time: i32,
+    rest_time: i32,
+    distance: i32,
+    score: i32,
+    is_flying: bool,
+    time_in_state: i32
+}
+
+pub fn race_reindeer_v2(reindeer: &mut Vec<Reindeer>, duration: i32) -> i32 {
+    let mut time_remaining = duration;
+    while time_remaining > 0 {
+
+    	let mut max_distance = 0;
+        
+        for racer in reindeer.iter_mut() {
+            // Moving on before the second rather than after it skips over flights or rests that last no time
+            while racer.time_in_state >= if racer.is_flying { racer.fly_time } else { racer.rest_time } {
+                racer.time_in_state = 0;
+                racer.is_flying = !racer.is_flying;
+            }
+
+            if racer.is_flying {
+                racer.distance += racer.speed;
+            }
+
+            if racer.distance > max_distance {
+                max_distance = racer.distance;
+            }
+
+            racer.time_in_state += 1;
+        }
+
+        for racer in reindeer.iter_mut() {
+            if racer.distance == max_distance {
+                racer.score += 1;
+            }
+        }
+
+        time_remaining -= 1;
+	}
+
+    let mut max_score = 0;
+    for racer in reindeer {
+        if racer.score > max_score {
+            max_score = racer.score;
+        }
+    }
+
+    max_score
+}
+
+pub fn race_reindeer(reindeer: &[Reindeer], duration: i32) -> i32 {
+    let mut distances: Vec<i32> = reindeer.iter().map(|p| {
+    	let mut dist = 0;
+    	let mut time_remaining = duration;
+        let mut is_flying = true;
+
+    	while time_remaining > 0 {
+    		let loop_duration = cmp::min(time_remaining, if is_flying { p.fly_time } else { p.rest_time });
+    		if is_flying {
+    		    dist += loop_duration * p.speed;
+    		}
+    		time_remaining -= loop_duration;
+    		is_flying = !is_flying;
+    	}
+
+        dist
+    }).collect();
+
+    distances.sort();
+
+    distances.last().copied().unwrap_or(0)
+}
+
+pub fn get_reindeer(input: &str) -> Result<Vec<Reindeer>, ParseError> {
+    let pattern = Pattern::new("{a reindeer name} can fly {a speed} km/s for {a flying time} seconds, but then must rest for {a resting time} seconds.");
+    let mut reindeer: Vec<Reindeer> = Vec::new();
+
+    for (line_number, line) in input.lines().enumerate() {
+        let on_line = |error: ParseError| error.on_line(line_number + 1);
+        let (name, speed_text, fly_text, rest_text): (&str, &str, &str, &str) = pattern.parse(line).map_err(on_line)?;
+
+        // Nothing goes backwards, and a reindeer that neither flies nor rests never gets any further through the race
+        let amount = |text: &str, expected: &str| -> Result<i32, ParseError> {
+            match token(line, text, expected)? {
+                x if x < 0 => Err(ParseError::at(line, text, expected)),
+                x => Ok(x),
+            }
+        };
+        let speed = amount(speed_text, "a speed").map_err(on_line)?;
+        let fly_time = amount(fly_text, "a flying time").map_err(on_line)?;
+        let rest_time = amount(rest_text, "a resting time").map_err(on_line)?;
+        if fly_time == 0 && rest_time == 0 {
+            return Err(on_line(ParseError::at(line, rest_text, "a resting time above zero for a reindeer that doesn't fly")));
+        }
+
+        trace!("{} flies at {} for {}s, then rests for {}s", name, speed, fly_time, rest_time);
+        reindeer.push(Reindeer{speed, fly_time, rest_time, 
+        						is_flying: true, time_in_state: 0, distance: 0, score: 0});
+    }
+
+    Ok(reindeer)
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    fn part_one(&self, input: &str, params: &Params) -> Result<Answer, ParseError> {
+        Ok(race_reindeer(&get_reindeer(input)?, params.get("race_length", 2503)?).into())
+    }
+
+    fn part_two(&self, input: &str, params: &Params) -> Result<Answer, ParseError> {
+        Ok(race_reindeer_v2(&mut get_reindeer(input)?, params.get("race_length", 2503)?).into())
+    }
+
+    // `size` reindeer (at least one), some of them identical so ties for the lead happen. Now and then
+    // one never flies or never rests, but never both.
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        let names = ["Vixen", "Rudolph", "Donner", "Blitzen", "Comet", "Cupid", "Dasher", "Dancer", "Prancer"];
+        let mut stats: Vec<(i64, i64, i64)> = Vec::new();
+        (0..std::cmp::max(1, size)).map(|i| {
+            let stat = match !stats.is_empty() && rng.one_in(5) {
+                true => *rng.pick(&stats),
+                false => match rng.below(6) {
+                    0 => (rng.range(1, 30), 0, rng.range(1, 200)),
+                    1 => (rng.range(1, 30), rng.range(1, 20), 0),
+                    _ => (rng.range(1, 30), rng.range(1, 20), rng.range(1, 200)),
+                },
+            };
+            stats.push(stat);
+            format!("{}{} can fly {} km/s for {} seconds, but then must rest for {} seconds.", names[i % names.len()], i / names.len(), stat.0, stat.1, stat.2)
+        }).collect::<Vec<String>>().join("\n")
+    }
+}
+
+#[test]
+fn bad_reindeer() {
+    let input = "Comet can fly 14 km/s for 10 seconds, but then must rest for 127 seconds.
+Dancer can fly fast km/s for 11 seconds, but then must rest for 162 seconds.";
+    assert_eq!(ParseError::new(2, 16, "fast", "a speed"), get_reindeer(input).unwrap_err());
+
+    let input = "Comet can fly 14 km/s for 10 seconds, but then must rest";
+    assert_eq!(ParseError::new(1, 57, "", "a resting time"), get_reindeer(input).unwrap_err());
+
+    // Neither flying nor resting would keep the race from ever finishing
+    let input = "Comet can fly 14 km/s for 10 seconds, but then must rest for 127 seconds.
+Dancer can fly 16 km/s for 0 seconds, but then must rest for 0 seconds.";
+    assert_eq!(ParseError::new(2, 62, "0", "a resting time above zero for a reindeer that doesn't fly"), get_reindeer(input).unwrap_err());
+    let input = "Comet can fly 14 km/s for -10 seconds, but then must rest for 127 seconds.";
+    assert_eq!(ParseError::new(1, 27, "-10", "a flying time"), get_reindeer(input).unwrap_err());
+    let input = "Comet can fly -14 km/s for 10 seconds, but then must rest for 127 seconds.";
+    assert_eq!(ParseError::new(1, 15, "-14", "a speed"), get_reindeer(input).unwrap_err());
+}
+
+#[test]
+fn empty_race() {
+    assert_eq!(0, race_reindeer(&[], 1000));
+    assert_eq!(Ok(Answer::Int(0)), Day14.part_one("", &Params::new()));
+    assert_eq!(Ok(Answer::Int(0)), Day14.part_two("", &Params::new()));
+}
+
+#[test]
+fn zero_times() {
+    // Never flying goes nowhere, and never resting flies the whole race
+    let params = Params::new().with("race_length", "10");
+    let grounded = "Comet can fly 14 km/s for 0 seconds, but then must rest for 5 seconds.";
+    assert_eq!(Ok(Answer::Int(0)), Day14.part_one(grounded, &params));
+    assert_eq!(Ok(Answer::Int(10)), Day14.part_two(grounded, &params));
+
+    let tireless = "Comet can fly 14 km/s for 3 seconds, but then must rest for 0 seconds.";
+    assert_eq!(Ok(Answer::Int(140)), Day14.part_one(tireless, &params));
+    let mut reindeer = get_reindeer(tireless).unwrap();
+    assert_eq!(10, race_reindeer_v2(&mut reindeer, 10));
+    assert_eq!(140, reindeer[0].distance);
+
+    // A sprinter leads for the first ten seconds, until the tireless one passes it while it rests
+    let input = format!("{}\nDancer can fly 50 km/s for 3 seconds, but then must rest for 10 seconds.", tireless);
+    let mut reindeer = get_reindeer(&input).unwrap();
+    assert_eq!(168, race_reindeer(&reindeer, 12));
+    assert_eq!(10, race_reindeer_v2(&mut reindeer, 12));
+    assert_eq!(vec![2, 10], reindeer.iter().map(|r| r.score).collect::<Vec<i32>>());
+}
+
+#[test]
+fn configured_race() {
+    let input = "Comet can fly 14 km/s for 10 seconds, but then must rest for 127 seconds.
+Dancer can fly 16 km/s for 11 seconds, but then must rest for 162 seconds.";
+
+    let params = Params::new().with("race_length", "1000");
+    assert_eq!(Ok(Answer::Int(1120)), Day14.part_one(input, &params));
+    assert_eq!(Ok(Answer::Int(689)), Day14.part_two(input, &params));
+}
+
+#[test]
+fn generated_races() {
+    for seed in 0..10 {
+        let input = Day14.generate(&mut Rng::new(seed), 12);
+        let duration = 500;
+        let mut reindeer = get_reindeer(&input).unwrap();
+        let winning_score = race_reindeer_v2(&mut reindeer, duration);
+
+        // Somebody scores every second, so the winner takes at least an even share and at most all of them
+        assert!(winning_score <= duration);
+        assert!(winning_score * reindeer.len() as i32 >= duration);
+        assert!(reindeer.iter().map(|r| r.score).sum::<i32>() >= duration);
+        assert_eq!(race_reindeer(&reindeer, duration), reindeer.iter().map(|r| r.distance).max().unwrap());
+    }
+}
+
+#[test]
+fn differential_distances() {
+    use aoc_core::differential::{shrink_lines, shrink_number, Differential};
+
+    // Both races track how far the leader got, one a whole flight or rest at a time and one second by second
+    Differential::new(|rng| {
+        let size = 1 + rng.below(8);
+        (Day14.generate(rng, size), rng.range(0, 1000))
+    })
+        .shrink_with(|(input, duration)| {
+            let fewer = shrink_lines(input).into_iter().filter(|i| !i.is_empty()).map(|i| (i, *duration));
+            fewer.chain(shrink_number(*duration).into_iter().map(|d| (input.clone(), d))).collect()
+        })
+        .implementation("whole flights", |(input, duration)| race_reindeer(&get_reindeer(input).unwrap(), *duration as i32))
+        .implementation("second by second", |(input, duration)| {
+            let mut reindeer = get_reindeer(input).unwrap();
+            race_reindeer_v2(&mut reindeer, *duration as i32);
+            reindeer.iter().map(|r| r.distance).max().unwrap()
+        })
+        .check(100);
+}
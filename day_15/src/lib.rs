@@ -0,0 +1,148 @@
+extern crate aoc_core;
+
+use std::cmp;
+use aoc_core::parse::{ParseError, field, parse_lines, token};
+use aoc_core::params::Params;
+use aoc_core::random::Rng;
+use aoc_core::solution::{Answer, Solution};
+
+#[derive(Debug)]
+pub struct Ingredient {
+    capacity: i32,
+    durability: i32,
+    flavor: i32,
+    texture: i32,
+    calories: i32,
+}
+
+impl Ingredient {
+    fn ensure_non_negative(&mut self) -> &Ingredient {
+		self.capacity = cmp::max(0, self.capacity);
+		self.durability = cmp::max(0, self.durability);
+		self.flavor = cmp::max(0, self.flavor);
+		self.texture = cmp::max(0, self.texture);
+
+		self
+	}
+
+	fn get_score(&self, calorie_target: Option<i32>) -> i32 {
+		if calorie_target.is_none() || calorie_target == Some(self.calories) {
+		    self.capacity * self.durability * self.flavor * self.texture
+		} else {
+		    0
+		}
+	}
+}
+
+pub fn get_best_cookie_score(ingredients: &[Ingredient], max_teaspoons: u32, calorie_target: Option<i32>) -> i32 {
+    get_capacities(ingredients.len(), max_teaspoons).iter().map(|x| get_cookie_score(ingredients, x, calorie_target)).max().unwrap_or(0)
+}
+
+fn get_capacities(num_ingredients: usize, max_teaspoons: u32) -> Vec<Vec<u32>> {	
+    if num_ingredients == 0 {
+        return Vec::new();
+    }
+    if num_ingredients == 1 {
+        return vec![vec![max_teaspoons]];
+    }
+
+    (0..max_teaspoons+1).flat_map(|x| {
+        let mut retval = get_capacities(num_ingredients - 1, max_teaspoons - x);
+
+        for combination in &mut retval {
+            combination.push(x);
+        }
+        retval
+    }).collect::<Vec<_>>()
+}
+
+fn get_cookie_score(ingredients: &[Ingredient], capacities: &[u32], calorie_target: Option<i32>) -> i32 {
+	let mut mixed_ingredients = Ingredient { capacity: 0, durability: 0, flavor: 0, texture: 0, calories: 0 };
+	for (i, teaspoon) in capacities.iter().enumerate() {
+	    mixed_ingredients.capacity += *teaspoon as i32 * (ingredients[i].capacity);
+		mixed_ingredients.durability += *teaspoon as i32 * (ingredients[i].durability);
+		mixed_ingredients.flavor += *teaspoon as i32 * (ingredients[i].flavor);
+		mixed_ingredients.texture += *teaspoon as i32 * (ingredients[i].texture);
+		mixed_ingredients.calories += *teaspoon as i32 * (ingredients[i].calories);
+	}
+
+	mixed_ingredients.ensure_non_negative().get_score(calorie_target)
+}
+
+pub fn get_ingredients(input: &str) -> Result<Vec<Ingredient>, ParseError> {
+    if input.lines().count() == 0 {
+        return Err(ParseError::at_end_of_input(input, "an ingredient"));
+    }
+
+    parse_lines(input, |l| {
+    	let split: Vec<&str> = l.split([' ', ':', ',']).collect();
+    	let property = |index: usize, expected: &str| -> Result<i32, ParseError> {
+    		token(l, field(l, &split, index, expected)?, expected)
+    	};
+
+        Ok(Ingredient {
+        	capacity: property(3, "a capacity")?,
+        	durability: property(6, "a durability")?,
+        	flavor: property(9, "a flavor")?,
+        	texture: property(12, "a texture")?,
+        	calories: property(15, "a calorie count")?
+        })
+    })
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    fn part_one(&self, input: &str, params: &Params) -> Result<Answer, ParseError> {
+        Ok(get_best_cookie_score(&get_ingredients(input)?, params.get("teaspoons", 100)?, None).into())
+    }
+
+    fn part_two(&self, input: &str, params: &Params) -> Result<Answer, ParseError> {
+        Ok(get_best_cookie_score(&get_ingredients(input)?, params.get("teaspoons", 100)?, Some(params.get("calories", 500)?)).into())
+    }
+
+    // `size` ingredients, at most four since every split of the teaspoons gets tried
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        let names = ["Sprinkles", "PeanutButter", "Frosting", "Sugar"];
+        names[..size.clamp(1, names.len())].iter().map(|name| {
+            format!("{}: capacity {}, durability {}, flavor {}, texture {}, calories {}",
+                    name, rng.range(-5, 5), rng.range(-5, 5), rng.range(-5, 5), rng.range(-5, 5), rng.range(1, 9))
+        }).collect::<Vec<String>>().join("\n")
+    }
+}
+
+#[test]
+fn best_cookie() {
+    let input = "Butterscotch: capacity -1, durability -2, flavor 6, texture 3, calories 8
+Cinnamon: capacity 2, durability 3, flavor -2, texture -1, calories 3";
+
+    let ingredients = get_ingredients(input).unwrap();
+    assert_eq!(62842880, get_best_cookie_score(&ingredients, 100, None));
+    assert_eq!(57600000, get_best_cookie_score(&ingredients, 100, Some(500)));
+}
+
+#[test]
+fn bad_ingredients() {
+    let input = "Butterscotch: capacity -1, durability -2, flavor 6, texture 3, calories 8
+Cinnamon: capacity 2, durability lots, flavor -2, texture -1, calories 3";
+    assert_eq!(ParseError::new(2, 34, "lots", "a durability"), get_ingredients(input).unwrap_err());
+}
+
+#[test]
+fn no_ingredients() {
+    assert_eq!(ParseError::new(1, 1, "", "an ingredient"), get_ingredients("").unwrap_err());
+    assert_eq!(0, get_best_cookie_score(&[], 100, None));
+}
+
+#[test]
+fn generated_cookies() {
+    for seed in 0..10 {
+        let ingredients = get_ingredients(&Day15.generate(&mut Rng::new(seed), 3)).unwrap();
+        let best = get_best_cookie_score(&ingredients, 30, None);
+
+        // Scores never go negative, and a calorie target can only rule recipes out
+        assert!(best >= 0);
+        assert!(get_best_cookie_score(&ingredients, 30, Some(150)) <= best);
+        assert!(get_best_cookie_score(&ingredients, 30, Some(150)) >= 0);
+    }
+}
@@ -0,0 +1,308 @@
+extern crate aoc_core;
+
+use aoc_core::grid::{Grid, Point};
+use aoc_core::parse::{ParseError, parse_lines};
+use aoc_core::params::Params;
+use aoc_core::random::Rng;
+use aoc_core::solution::{Answer, Solution};
+
+// A keypad drawn one character per key, with spaces and the `blank` marker for gaps. Rows can be
+// any length, so keypads don't have to be square or even rectangular.
+pub struct Keypad {
+    keys: Grid<Option<char>>,
+}
+
+impl Keypad {
+    pub fn parse(drawing: &str, blank: char) -> Result<Keypad, ParseError> {
+        let lines = drawing.lines().collect::<Vec<&str>>();
+        let width = lines.iter().map(|line| line.chars().count()).max().unwrap_or(0);
+        let mut keys = Grid::new(width, lines.len(), None);
+
+        for (y, line) in lines.iter().enumerate() {
+            for (x, (index, c)) in line.char_indices().enumerate() {
+                if c == blank || c.is_whitespace() {
+                    continue;
+                }
+                if keys.cells().any(|&key| key == Some(c)) {
+                    return Err(ParseError::at(line, &line[index..index + c.len_utf8()], "a key label not already on the keypad").on_line(y + 1));
+                }
+                keys[(x as i32, y as i32)] = Some(c);
+            }
+        }
+
+        if keys.cells().all(Option::is_none) {
+            let last_line = lines.last().copied().unwrap_or("");
+            return Err(ParseError::at_end(last_line, "a keypad drawing with at least one key").on_line(lines.len().max(1)));
+        }
+        Ok(Keypad { keys })
+    }
+
+    pub fn find(&self, label: char) -> Option<Point> {
+        self.keys.points().find(|&point| self.keys[point] == Some(label))
+    }
+
+    // Every key, reading along the rows from the top
+    pub fn labels(&self) -> Vec<char> {
+        self.keys.cells().flatten().copied().collect()
+    }
+
+    // Moves that would leave the keypad or land on a gap are ignored
+    fn move_key(&self, from: Point, dir: Point) -> Result<Point, Obstacle> {
+        let new_loc = (from.0 + dir.0, from.1 + dir.1);
+        match self.keys.get(new_loc) {
+            Some(Some(_)) => Ok(new_loc),
+            Some(None) => Err(Obstacle::Gap),
+            None => Err(Obstacle::Edge)
+        }
+    }
+
+    // The keypad with the finger's key in brackets
+    pub fn draw(&self, finger: Point) -> String {
+        self.keys.rows().enumerate().map(|(y, row)| {
+            row.iter().enumerate().map(|(x, key)| match *key {
+                Some(label) if finger == (x as i32, y as i32) => format!("[{}]", label),
+                Some(label) => format!(" {} ", label),
+                None => "   ".to_string(),
+            }).collect::<String>().trim_end().to_string()
+        }).collect::<Vec<String>>().join("\n")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Obstacle {
+    Edge,
+    Gap
+}
+
+// What the finger did for one letter of the instructions, or at the end of a line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    Moved { direction: char, from: Point, to: Point },
+    Blocked { direction: char, at: Point, by: Obstacle },
+    Pressed { at: Point, key: char },
+}
+
+fn to_direction(line: &str, index: usize, from: char) -> Result<(char, Point), ParseError> {
+    match from {
+        'U' => Ok(('U', (0,-1))),
+        'L' => Ok(('L', (-1,0))),
+        'D' => Ok(('D', (0,1))),
+        'R' => Ok(('R', (1,0))),
+        _   => Err(ParseError::at(line, &line[index..index + from.len_utf8()], "a direction letter U, D, L or R"))
+    }
+}
+
+fn parse_moves(line: &str) -> Result<Vec<(char, Point)>, ParseError> {
+    line.char_indices().map(|(i, c)| to_direction(line, i, c)).collect()
+}
+
+// Every move the finger makes or can't make, and each key it presses at the end of a line
+pub fn trace_bathroom_code(input_str: &str, keypad : &Keypad, start : char) -> Result<Vec<Step>, ParseError> {
+    let starting_position = match keypad.find(start) {
+        Some(position) => position,
+        None => return Err(ParseError::new(1, 1, &start.to_string(), "a key on the keypad to start from")),
+    };
+
+    let mut position = starting_position;
+    let mut steps = Vec::new();
+    for moves in parse_lines(input_str, parse_moves)? {
+        for (direction, dir) in moves {
+            match keypad.move_key(position, dir) {
+                Ok(to) => {
+                    steps.push(Step::Moved { direction, from: position, to });
+                    position = to;
+                },
+                Err(by) => steps.push(Step::Blocked { direction, at: position, by }),
+            }
+        }
+        steps.push(Step::Pressed { at: position, key: keypad.keys[position].unwrap() });
+    }
+    Ok(steps)
+}
+
+pub fn get_bathroom_code(input_str: &str, keypad : &Keypad, start : char) -> Result<String, ParseError> {
+    Ok(trace_bathroom_code(input_str, keypad, start)?.into_iter().filter_map(|step| match step {
+        Step::Pressed { key, .. } => Some(key),
+        _ => None,
+    }).collect())
+}
+
+// A frame for the start and for every step of a trace: the keypad with the finger on it, the code
+// so far and what just happened
+pub fn replay_frames(keypad: &Keypad, start: char, steps: &[Step]) -> Vec<String> {
+    let mut finger = keypad.find(start).expect("The trace started from a key on the keypad");
+    let mut code = String::new();
+    let mut frames = vec![format!("{}\n\nCode: \nStarting on {}", keypad.draw(finger), start)];
+
+    for step in steps {
+        let happened = match *step {
+            Step::Moved { direction, to, .. } => {
+                finger = to;
+                format!("Moved {}", direction)
+            },
+            Step::Blocked { direction, by: Obstacle::Edge, .. } => format!("{} is blocked by the edge", direction),
+            Step::Blocked { direction, by: Obstacle::Gap, .. } => format!("{} is blocked by a gap", direction),
+            Step::Pressed { key, .. } => {
+                code.push(key);
+                format!("Pressed {}", key)
+            },
+        };
+        frames.push(format!("{}\n\nCode: {}\n{}", keypad.draw(finger), code, happened));
+    }
+    frames
+}
+
+pub fn get_part_one_keypad() -> Keypad {
+    Keypad::parse(include_str!("../keypads/part_one.txt"), ' ').unwrap()
+}
+
+pub fn get_part_two_keypad() -> Keypad {
+    Keypad::parse(include_str!("../keypads/part_two.txt"), ' ').unwrap()
+}
+
+// A keypad drawing named by the `key` parameter, or the puzzle's own
+fn configured_keypad(params: &Params, key: &str, default: fn() -> Keypad) -> Result<Keypad, ParseError> {
+    match params.read_file(key)? {
+        Some((file, drawing)) => Keypad::parse(&drawing, params.get("blank", ' ')?).map_err(|e| e.in_file(&file)),
+        None => Ok(default()),
+    }
+}
+
+// The `start` key, which has to be on the keypad it starts on
+fn configured_start(params: &Params, keypad: &Keypad) -> Result<char, ParseError> {
+    let start = params.get("start", '5')?;
+    match keypad.find(start) {
+        Some(_) => Ok(start),
+        None => Err(params.reject("start", "a key on the keypad")),
+    }
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    fn part_one(&self, input: &str, params: &Params) -> Result<Answer, ParseError> {
+        let keypad = configured_keypad(params, "part_one_keypad", get_part_one_keypad)?;
+        Ok(get_bathroom_code(input, &keypad, configured_start(params, &keypad)?)?.into())
+    }
+
+    fn part_two(&self, input: &str, params: &Params) -> Result<Answer, ParseError> {
+        let keypad = configured_keypad(params, "part_two_keypad", get_part_two_keypad)?;
+        Ok(get_bathroom_code(input, &keypad, configured_start(params, &keypad)?)?.into())
+    }
+
+    // `size` lines of moves, some long enough to run into the keypad's edges
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        (0..size).map(|_| {
+            let length = rng.range(1, 40) as usize;
+            rng.word("UDLR", length)
+        }).collect::<Vec<String>>().join("\n")
+    }
+}
+
+#[test]
+fn part_one() {
+    let inputs = "ULL
+RRDDD
+LURDL
+UUUUD";
+    assert_eq!(Ok("1985".to_string()), get_bathroom_code(inputs, &get_part_one_keypad(), '5'));
+}
+
+#[test]
+fn part_two() {
+    let inputs = "ULL
+RRDDD
+LURDL
+UUUUD";
+    assert_eq!(Ok("5DB3".to_string()), get_bathroom_code(inputs, &get_part_two_keypad(), '5'));
+}
+
+#[test]
+fn bad_direction() {
+    let inputs = "ULL
+RRDXD";
+    assert_eq!(Err(ParseError::new(2, 4, "X", "a direction letter U, D, L or R")), get_bathroom_code(inputs, &get_part_one_keypad(), '5'));
+}
+
+#[test]
+fn generated_codes() {
+    for seed in 0..20 {
+        let input = Day2.generate(&mut Rng::new(seed), 6);
+        let code = get_bathroom_code(&input, &get_part_two_keypad(), '5').unwrap();
+        assert_eq!(6, code.len());
+        assert!(code.chars().all(|c| get_part_two_keypad().labels().contains(&c)));
+
+        // A line that walks far enough up and left always ends on the top-left key, wherever it started
+        let pinned = input.lines().map(|line| line.to_string() + "UULL").collect::<Vec<String>>().join("\n");
+        assert_eq!("111111", get_bathroom_code(&pinned, &get_part_one_keypad(), '5').unwrap());
+    }
+}
+
+#[test]
+fn drawn_keypads() {
+    // A ragged phone keypad, with '#' for the hole next to 0
+    let keypad = Keypad::parse("123\n456\n789\n#0", '#').unwrap();
+    assert_eq!(vec!['1', '2', '3', '4', '5', '6', '7', '8', '9', '0'], keypad.labels());
+    assert_eq!(Some((1,3)), keypad.find('0'));
+    assert_eq!(None, keypad.find('#'));
+    assert_eq!(Ok("0618".to_string()), get_bathroom_code("DDD\nURRU\nLUL\nRUDD", &keypad, '5'));
+
+    assert_eq!(vec!['1', '2', '3', '4', '5', '6', '7', '8', '9', 'A', 'B', 'C', 'D'], get_part_two_keypad().labels());
+    assert_eq!(Some((0,2)), get_part_two_keypad().find('5'));
+}
+
+#[test]
+fn bad_keypads() {
+    assert_eq!(Err(ParseError::new(2, 2, "1", "a key label not already on the keypad")), Keypad::parse("12\n-1", '-').map(|_| ()));
+    assert_eq!(Err(ParseError::new(1, 4, "", "a keypad drawing with at least one key")), Keypad::parse("- -", '-').map(|_| ()));
+    assert_eq!(Err(ParseError::new(2, 4, "", "a keypad drawing with at least one key")), Keypad::parse("---\n- -", '-').map(|_| ()));
+    assert_eq!(Err(ParseError::new(1, 1, "E", "a key on the keypad to start from")), get_bathroom_code("U", &get_part_two_keypad(), 'E'));
+
+    // A start that isn't a key is blamed on the parameter, or on its absence when the default doesn't fit
+    assert_eq!(Err(ParseError::new(1, 9, "E", "a key on the keypad for start")), Day2.part_two("U", &Params::new().with("start", "E")));
+}
+
+#[test]
+fn configured_keypads() {
+    let dir = std::env::temp_dir().join(format!("aoc-day-2-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("input.ini"), "part_one_keypad = wide.txt\npart_two_keypad = bad.txt\nblank = .\nstart = b").unwrap();
+    std::fs::write(dir.join("wide.txt"), "abcd\n.ef.").unwrap();
+    std::fs::write(dir.join("bad.txt"), "ab\nba").unwrap();
+
+    let params = Params::load(dir.join("input.ini")).unwrap();
+    assert_eq!(Ok(Answer::Text("ddc".to_string())), Day2.part_one("RRR\nD\nL", &params));
+    let error = Day2.part_two("U", &params).unwrap_err();
+    assert_eq!(Some(dir.join("bad.txt").display().to_string()), error.file);
+    assert_eq!((2, 1, "b"), (error.line, error.column, error.token.as_str()));
+
+    // The puzzle's start of 5 isn't on the wide keypad, so it has to be set
+    std::fs::write(dir.join("input.ini"), "part_one_keypad = wide.txt\nblank = .").unwrap();
+    let params = Params::load(dir.join("input.ini")).unwrap();
+    let expected = ParseError::new(1, 1, "", "a key on the keypad for start, which isn't set").in_file(&dir.join("input.ini").display().to_string());
+    assert_eq!(Err(expected), Day2.part_one("U", &params));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn traced_presses() {
+    let keypad = get_part_two_keypad();
+    let steps = trace_bathroom_code("ULR\nDDD", &keypad, '5').unwrap();
+    assert_eq!(vec![
+        Step::Blocked { direction: 'U', at: (0,2), by: Obstacle::Gap },
+        Step::Blocked { direction: 'L', at: (0,2), by: Obstacle::Edge },
+        Step::Moved { direction: 'R', from: (0,2), to: (1,2) },
+        Step::Pressed { at: (1,2), key: '6' },
+        Step::Moved { direction: 'D', from: (1,2), to: (1,3) },
+        Step::Blocked { direction: 'D', at: (1,3), by: Obstacle::Gap },
+        Step::Blocked { direction: 'D', at: (1,3), by: Obstacle::Gap },
+        Step::Pressed { at: (1,3), key: 'A' },
+    ], steps);
+
+    let frames = replay_frames(&keypad, '5', &steps);
+    assert_eq!(9, frames.len());
+    assert_eq!("       1\n    2  3  4\n[5] 6  7  8  9\n    A  B  C\n       D\n\nCode: \nStarting on 5", frames[0]);
+    assert!(frames[2].ends_with("Code: \nL is blocked by the edge"));
+    assert_eq!("       1\n    2  3  4\n 5  6  7  8  9\n   [A] B  C\n       D\n\nCode: 6A\nPressed A", frames[8]);
+}
@@ -0,0 +1,359 @@
+extern crate aoc_core;
+extern crate permutohedron;
+
+use std::iter::FromIterator;
+use permutohedron::Heap;
+use aoc_core::parse::{ParseError, field, parse_lines};
+use aoc_core::pattern::{FromCapture, Pattern};
+use aoc_core::params::Params;
+use aoc_core::random::Rng;
+use aoc_core::solution::{Answer, Solution};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Direction {
+    Left,
+    Right,
+}
+
+impl<'a> FromCapture<'a> for Direction {
+    fn from_capture(line: &'a str, text: &'a str, expected: &str) -> Result<Direction, ParseError> {
+        match text {
+            "left" => Ok(Direction::Left),
+            "right" => Ok(Direction::Right),
+            _ => Err(ParseError::at(line, text, expected))
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Instruction {
+    SwapPosition{x: usize, y: usize},
+    SwapLetters{a: char, b: char},
+    Rotate{x: usize, dir: Direction},
+    RotateLetter{a: char},
+    Reverse{x: usize, y: usize},
+    Move{x: usize, y: usize}
+}
+
+fn swap(chars: Vec<char>, x: usize, y: usize) -> Vec<char> {
+	let mut copy = chars.to_vec();
+	copy.swap(x, y);
+	copy
+}
+
+fn swap_letters(chars: Vec<char>, a: char, b: char) -> Vec<char> {
+	let pos_x = chars.iter().position(|x| *x == a).unwrap();
+	let pos_y = chars.iter().position(|x| *x == b).unwrap();
+	swap(chars, pos_x, pos_y)
+}
+
+fn rotate(chars: Vec<char>, x: usize, dir: Direction) -> Vec<char> {
+	if chars.is_empty() {
+		return chars;
+	}
+	let rotator = match dir {
+		Direction::Left => x % chars.len(),
+		Direction::Right => chars.len() - (x % chars.len())
+	};
+
+	chars[..rotator].iter().rev().chain(chars[rotator..].iter().rev()).rev().copied().collect()
+}
+
+fn rotate_letter(chars: Vec<char>, a: char) -> Vec<char> {
+	let pos_char = chars.iter().position(|x| *x == a).unwrap();
+	let rotator = pos_char + if pos_char >= 4 {2} else {1};
+	rotate(chars, rotator, Direction::Right)
+}
+
+fn reverse(chars: Vec<char>, x: usize, y: usize) -> Vec<char> {
+	let start_index = std::cmp::max(0, x) as usize;
+	let end_index = std::cmp::min(chars.len() - 1, y) as usize + 1;
+
+	chars[..start_index].iter().chain(chars[start_index..end_index].iter().rev().chain(chars[end_index..].iter())).copied().collect()
+}
+
+fn move_position(mut chars: Vec<char>, x: usize, y: usize) -> Vec<char> {
+	let c = chars.remove(x);
+	chars.insert(y, c);
+	chars
+}
+
+const SWAP_POSITION: &str = "swap position {a position} with position {a position}";
+const SWAP_LETTER: &str = "swap letter {a letter} with letter {a letter}";
+const REVERSE: &str = "reverse positions {a position} through {a position}";
+const ROTATE_LETTER: &str = "rotate based on position of letter {a letter}";
+const ROTATE: &str = "rotate {left or right} {a number of steps} step|steps";
+const MOVE: &str = "move position {a position} to position {a position}";
+
+fn parse_instruction(line: &str) -> Result<Instruction, ParseError> {
+	let split : Vec<&str> = line.split_whitespace().collect();
+	match field(line, &split, 0, "an instruction")? {
+		"swap" => {
+			match field(line, &split, 1, "position or letter")? {
+				"position" => {
+					let (x, y) = Pattern::new(SWAP_POSITION).parse(line)?;
+					Ok(Instruction::SwapPosition{x, y})
+				},
+				"letter" => {
+					let (a, b) = Pattern::new(SWAP_LETTER).parse(line)?;
+					Ok(Instruction::SwapLetters{a, b})
+				},
+				x => Err(ParseError::at(line, x, "position or letter"))
+			}
+		},
+		"reverse" => {
+			let (x, y) = Pattern::new(REVERSE).parse(line)?;
+			Ok(Instruction::Reverse{x, y})
+		},
+		"rotate" => {
+			match field(line, &split, 1, "left, right or based")? {
+				"based" => {
+					let a = Pattern::new(ROTATE_LETTER).parse(line)?;
+					Ok(Instruction::RotateLetter{a})
+				},
+				"left" | "right" => {
+					let (dir, x) = Pattern::new(ROTATE).parse(line)?;
+					Ok(Instruction::Rotate{dir, x})
+				},
+				x => Err(ParseError::at(line, x, "left, right or based"))
+			}
+		},
+		"move" => {
+			let (x, y) = Pattern::new(MOVE).parse(line)?;
+			Ok(Instruction::Move{x, y})
+		},
+		x => Err(ParseError::at(line, x, "swap, reverse, rotate or move"))
+	}
+}
+
+// Instructions only move letters around, so every position and letter they name has to be in the
+// password. The instruction's pattern captures the text of each one again to blame it.
+fn check_instruction(line: &str, instruction: Instruction, password: &[char]) -> Result<Instruction, ParseError> {
+	let position = |text: &str, x: usize| match x < password.len() {
+		true => Ok(()),
+		false => Err(ParseError::at(line, text, "a position within the password")),
+	};
+	let letter = |text: &str, a: char| match password.contains(&a) {
+		true => Ok(()),
+		false => Err(ParseError::at(line, text, "a letter in the password")),
+	};
+	let positions = |(x_text, y_text): (&str, &str), (x, y): (usize, usize)| {
+		position(x_text, x)?;
+		position(y_text, y)
+	};
+
+	match instruction {
+		Instruction::SwapPosition{x, y} => positions(Pattern::new(SWAP_POSITION).parse(line)?, (x, y))?,
+		Instruction::Move{x, y} => positions(Pattern::new(MOVE).parse(line)?, (x, y))?,
+		Instruction::SwapLetters{a, b} => {
+			let (a_text, b_text) = Pattern::new(SWAP_LETTER).parse(line)?;
+			letter(a_text, a)?;
+			letter(b_text, b)?;
+		},
+		Instruction::RotateLetter{a} => letter(Pattern::new(ROTATE_LETTER).parse(line)?, a)?,
+		Instruction::Reverse{x, y} => {
+			let (x_text, y_text) = Pattern::new(REVERSE).parse(line)?;
+			positions((x_text, y_text), (x, y))?;
+			if y < x {
+				return Err(ParseError::at(line, y_text, "a position no earlier than the first"));
+			}
+		},
+		Instruction::Rotate{..} => (),
+	}
+	Ok(instruction)
+}
+
+fn parse_instructions(input: &str, password: &str) -> Result<Vec<Instruction>, ParseError> {
+	let password : Vec<char> = password.chars().collect();
+	parse_lines(input, |line| parse_instruction(line).and_then(|instruction| check_instruction(line, instruction, &password)))
+}
+
+fn apply_instructions(instructions: &[Instruction], starting_string: &str) -> String {
+    instructions.iter().fold(starting_string.to_string().chars().collect(), |char_array, instruction|{
+    	match *instruction {
+		    Instruction::SwapPosition{x, y} => swap(char_array, x, y),
+		    Instruction::SwapLetters{a, b} => swap_letters(char_array, a, b),
+		    Instruction::Rotate{x, dir} => rotate(char_array, x, dir),
+		    Instruction::RotateLetter{a} => rotate_letter(char_array, a),
+		    Instruction::Reverse{x, y} => reverse(char_array, x, y),
+		    Instruction::Move{x, y} => move_position(char_array, x, y)
+    	}
+    }).into_iter().collect()
+}
+
+pub fn run_instructions(input: &str, starting_string: &str) -> Result<String, ParseError> {
+	Ok(apply_instructions(&parse_instructions(input, starting_string)?, starting_string))
+}
+
+pub fn reverse_engineer(input: &str, desired_output: &str) -> Result<String, ParseError> {
+	let instructions = parse_instructions(input, desired_output)?;
+	let mut initial : Vec<char> = desired_output.to_string().chars().collect();
+	let heap = Heap::new(&mut initial);
+
+	for brute_attempt in heap {
+		let output = apply_instructions(&instructions, &String::from_iter(brute_attempt.to_vec()));
+		if output == desired_output {
+			return Ok(brute_attempt.iter().copied().collect());
+		}
+	}
+
+	let first_line = input.lines().next().unwrap_or("");
+	Err(ParseError::at(first_line, first_line, "instructions that some password scrambles into the one given"))
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+	fn part_one(&self, input: &str, params: &Params) -> Result<Answer, ParseError> {
+		Ok(run_instructions(input, params.get_str("password", "abcdefgh"))?.into())
+	}
+
+	fn part_two(&self, input: &str, params: &Params) -> Result<Answer, ParseError> {
+		Ok(reverse_engineer(input, params.get_str("scrambled", "fbgdceah"))?.into())
+	}
+
+	// `size` instructions for eight letter passwords, with the do-nothing cases such as zero step
+	// rotations and swapping a position with itself left in
+	fn generate(&self, rng: &mut Rng, size: usize) -> String {
+		generate_instructions(rng, size, "abcdefgh")
+	}
+}
+
+fn generate_instructions(rng: &mut Rng, size: usize, letters: &str) -> String {
+	let last = letters.len() as i64 - 1;
+	(0..size).map(|_| match rng.below(6) {
+		0 => format!("swap position {} with position {}", rng.range(0, last), rng.range(0, last)),
+		1 => format!("swap letter {} with letter {}", rng.word(letters, 1), rng.word(letters, 1)),
+		2 => {
+			let steps = rng.range(0, 2 * last);
+			format!("rotate {} {} step{}", rng.pick(&["left", "right"]), steps, if steps == 1 { "" } else { "s" })
+		},
+		3 => format!("rotate based on position of letter {}", rng.word(letters, 1)),
+		4 => {
+			let (x, y) = (rng.range(0, last), rng.range(0, last));
+			format!("reverse positions {} through {}", std::cmp::min(x, y), std::cmp::max(x, y))
+		},
+		_ => format!("move position {} to position {}", rng.range(0, last), rng.range(0, last)),
+	}).collect::<Vec<String>>().join("\n")
+}
+
+#[test]
+fn full() {
+	let input = "swap position 4 with position 0
+swap letter d with letter b
+reverse positions 0 through 4
+rotate left 1 step
+move position 1 to position 4
+move position 3 to position 0
+rotate based on position of letter b
+rotate based on position of letter d";
+
+	assert_eq!(Ok("decab".to_string()), run_instructions(input, "abcde"));
+}
+
+#[test]
+fn bad_instructions() {
+	assert_eq!(Err(ParseError::new(1, 1, "shuffle", "swap, reverse, rotate or move")), parse_instruction("shuffle all letters"));
+	assert_eq!(Err(ParseError::new(1, 13, "dd", "a letter")), parse_instruction("swap letter dd with letter b"));
+	assert_eq!(Err(ParseError::new(1, 8, "up", "left, right or based")), parse_instruction("rotate up 1 step"));
+	assert_eq!(Err(ParseError::new(2, 15, "four", "a position")), run_instructions("rotate left 1 step\nmove position four to position 0", "abcde"));
+	assert_eq!(Err(ParseError::new(1, 28, "", "a position")), parse_instruction("reverse positions 0 through"));
+	assert_eq!(Err(ParseError::new(1, 17, "and", "\"with\"")), parse_instruction("swap position 4 and position 0"));
+}
+
+#[test]
+fn instructions_outside_the_password() {
+	assert_eq!(Err(ParseError::new(1, 31, "5", "a position within the password")), run_instructions("swap position 0 with position 5", "abcde"));
+	assert_eq!(Err(ParseError::new(2, 13, "z", "a letter in the password")), run_instructions("rotate left 1 step\nswap letter z with letter a", "abcde"));
+	assert_eq!(Err(ParseError::new(1, 36, "x", "a letter in the password")), run_instructions("rotate based on position of letter x", "abcde"));
+	assert_eq!(Err(ParseError::new(1, 19, "9", "a position within the password")), run_instructions("reverse positions 9 through 9", "abcde"));
+	assert_eq!(Err(ParseError::new(1, 29, "1", "a position no earlier than the first")), run_instructions("reverse positions 3 through 1", "abcde"));
+	assert_eq!(Err(ParseError::new(1, 15, "7", "a position within the password")), reverse_engineer("move position 7 to position 0", "abcde"));
+	assert_eq!(Ok(String::new()), run_instructions("rotate right 3 steps", ""));
+}
+
+#[test]
+fn swap_test() {
+	let chars = "abcde".to_string().chars().collect();
+	assert_eq!("ebcda", String::from_iter(swap(chars, 4, 0).into_iter()));
+}
+
+#[test]
+fn swap_letter_test() {
+	let chars = "ebcda".to_string().chars().collect();
+	assert_eq!("edcba", String::from_iter(swap_letters(chars, 'd', 'b').into_iter()));
+}
+
+#[test]
+fn reverse_test() {
+	let chars = "edcba".to_string().chars().collect();
+	assert_eq!("abcde", String::from_iter(reverse(chars, 0, 4).into_iter()));
+}
+
+#[test]
+fn rotate_test() {
+	let chars = "abcde".to_string().chars().collect();
+	assert_eq!("bcdea", String::from_iter(rotate(chars, 1, Direction::Left).into_iter()));
+}
+
+#[test]
+fn move_test() {
+	let mut chars = "bcdea".to_string().chars().collect();
+	assert_eq!("bdeac", String::from_iter(move_position(chars, 1, 4).into_iter()));
+
+	chars = "bdeac".to_string().chars().collect();
+	assert_eq!("abdec", String::from_iter(move_position(chars, 3, 0).into_iter()));
+}
+
+#[test]
+fn rotate_letter_test() {
+	let mut chars = "abdec".to_string().chars().collect();
+	assert_eq!("ecabd", String::from_iter(rotate_letter(chars, 'b').into_iter()));
+
+	chars = "ecabd".to_string().chars().collect();
+	assert_eq!("decab", String::from_iter(rotate_letter(chars, 'd').into_iter()));
+}
+
+#[test]
+fn configured_passwords() {
+	let input = "swap position 4 with position 0
+swap letter d with letter b
+reverse positions 0 through 4
+rotate left 1 step";
+
+	let params = Params::new().with("password", "abcde").with("scrambled", "bcdea");
+	assert_eq!(Ok(Answer::Text("bcdea".to_string())), Day21.part_one(input, &params));
+	assert_eq!(Ok(Answer::Text("abcde".to_string())), Day21.part_two(input, &params));
+}
+
+#[test]
+fn unscrambling() {
+	// Every instruction shuffles eight letters one-to-one, so unscrambling gets back the original
+	for seed in 0..10 {
+		let mut rng = Rng::new(seed);
+		let input = Day21.generate(&mut rng, 10);
+		let mut password = "abcdefgh".chars().collect::<Vec<char>>();
+		rng.shuffle(&mut password);
+		let password = String::from_iter(password);
+
+		let scrambled = run_instructions(&input, &password).unwrap();
+		assert_eq!(Ok(password), reverse_engineer(&input, &scrambled));
+	}
+}
+
+#[test]
+fn differential_unscrambling() {
+	use aoc_core::differential::{shrink_lines, Differential};
+
+	// Running the instructions forwards and then searching for what scrambles to the result goes in a circle
+	Differential::new(|rng| {
+		let size = 1 + rng.below(8);
+		let mut password = "abcdefgh".chars().collect::<Vec<char>>();
+		rng.shuffle(&mut password);
+		(generate_instructions(rng, size, "abcdefgh"), String::from_iter(password))
+	})
+		.shrink_with(|(input, password)| shrink_lines(input).into_iter().map(|i| (i, password.clone())).collect())
+		.implementation("password", |(_, password)| password.clone())
+		.implementation("unscrambled", |(input, password)| reverse_engineer(input, &run_instructions(input, password).unwrap()).unwrap())
+		.check(10);
+}
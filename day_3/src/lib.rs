@@ -0,0 +1,107 @@
+extern crate aoc_core;
+
+use aoc_core::parse::{ParseError, field, parse_lines, token};
+use aoc_core::params::Params;
+use aoc_core::random::Rng;
+use aoc_core::solution::{Answer, Solution};
+
+struct Triangle(i32, i32, i32);
+
+fn is_valid_triangle(triangle: &Triangle) -> bool {
+    ((triangle.0 + triangle.1) > triangle.2) && 
+    ((triangle.1 + triangle.2) > triangle.0) &&
+    ((triangle.2 + triangle.0) > triangle.1) 
+}
+
+fn parse_triangle(line: &str) -> Result<Triangle, ParseError> {
+    let split = line.split_whitespace().collect::<Vec<&str>>();
+    if split.len() > 3 {
+        return Err(ParseError::at(line, split[3], "only three side lengths"));
+    }
+
+    let side = |i| field(line, &split, i, "a side length").and_then(|s| token(line, s, "a side length"));
+    Ok(Triangle(side(0)?, side(1)?, side(2)?))
+}
+
+pub fn get_num_triangles_by_row(input_str: &str) -> Result<usize, ParseError> {
+   Ok(parse_lines(input_str, parse_triangle)?.iter().filter(|t| is_valid_triangle(t)).count())
+}
+
+pub fn get_num_triangles_by_col(input_str: &str) -> Result<usize, ParseError> {
+    let rows = parse_lines(input_str, parse_triangle)?;
+    if rows.len() % 3 != 0 {
+        return Err(ParseError::new(rows.len() + 1, 1, "", "rows in groups of three"));
+    }
+
+    Ok(rows.chunks(3).map( |s| {
+        vec!(Triangle(s[0].0, s[1].0, s[2].0), Triangle(s[0].1, s[1].1, s[2].1), Triangle(s[0].2, s[1].2, s[2].2))
+    }).fold(0, |outer_count, outer| {
+        outer.iter().fold(outer_count, | inner_count, inner | {
+            inner_count + (is_valid_triangle(inner) as usize)
+        })
+    }))
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    fn part_one(&self, input: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(get_num_triangles_by_row(input)?.into())
+    }
+
+    fn part_two(&self, input: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(get_num_triangles_by_col(input)?.into())
+    }
+
+    // `size` rows, rounded up to a whole number of column groups, with some exactly degenerate triangles
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        let rows = std::cmp::max(3, size.div_ceil(3) * 3);
+        (0..rows).map(|_| {
+            let (a, b) = (rng.range(1, 500), rng.range(1, 500));
+            let c = match rng.one_in(5) {
+                true => a + b,
+                false => rng.range(1, 999),
+            };
+            format!("{:>5}{:>5}{:>5}", a, b, c)
+        }).collect::<Vec<String>>().join("\n")
+    }
+}
+
+#[test]
+fn part_one() {
+    let inputs = "5 10 25";
+    assert_eq!(Ok(0), get_num_triangles_by_row(inputs));
+}
+
+#[test]
+fn part_two() {
+    let inputs = "101 301 501
+102 302 502
+103 303 503
+201 401 601
+202 402 602
+203 403 603";
+    assert_eq!(Ok(6), get_num_triangles_by_col(inputs));
+}
+
+#[test]
+fn bad_triangles() {
+    assert_eq!(Err(ParseError::new(2, 8, "", "a side length")), get_num_triangles_by_row("1 2 3\n  4   5"));
+    assert_eq!(Err(ParseError::new(1, 3, "b", "a side length")), get_num_triangles_by_row("1 b 3"));
+    assert_eq!(Err(ParseError::new(1, 7, "4", "only three side lengths")), get_num_triangles_by_row("1 2 3 4"));
+    assert_eq!(Err(ParseError::new(2, 1, "", "rows in groups of three")), get_num_triangles_by_col("1 2 3"));
+}
+
+#[test]
+fn transposed_columns() {
+    // Reading each block of three rows by column is the same as reading its transpose by row
+    for seed in 0..20 {
+        let input = Day3.generate(&mut Rng::new(seed), 30);
+        let rows = input.lines().map(|line| line.split_whitespace().collect::<Vec<&str>>()).collect::<Vec<Vec<&str>>>();
+        let transposed = rows.chunks(3).flat_map(|block| (0..3).map(move |i| format!("{} {} {}", block[0][i], block[1][i], block[2][i])))
+            .collect::<Vec<String>>().join("\n");
+
+        assert_eq!(get_num_triangles_by_col(&input), get_num_triangles_by_row(&transposed));
+        assert_eq!(get_num_triangles_by_row(&input), get_num_triangles_by_col(&transposed));
+    }
+}
@@ -0,0 +1,183 @@
+extern crate aoc_core;
+
+use std::collections::HashMap;
+use std::cmp::Ordering;
+use aoc_core::parse::{ParseError, parse_lines, token};
+use aoc_core::params::Params;
+use aoc_core::random::{LOWERCASE, Rng};
+use aoc_core::solution::{Answer, Solution};
+
+#[derive(Debug)]
+struct RoomLetter(char,u32);
+struct Room(String,u32,String,String);
+
+fn generate_checksum(room_name: &str) -> String {
+    let mut char_map : HashMap<char, u32> = HashMap::new();
+    for c in room_name.chars() {
+        let count = match char_map.get(&c) {
+            Some(x) => x + 1,
+            None => 1
+        };
+        char_map.insert(c, count);
+    }
+
+    let mut checksum_vec : Vec<RoomLetter> = char_map.iter().map(|(c,x)| {
+        RoomLetter(*c,*x)
+    }).collect();
+
+    checksum_vec.sort_by(|a,b|{
+        match b.1.cmp(&a.1) {
+            Ordering::Equal => a.0.cmp(&b.0),
+            x => x
+        }
+    });
+
+    checksum_vec.iter().map(|x| x.0).take(5).collect()
+}
+
+fn parse_room(entry: &str) -> Result<Room, ParseError> {
+    let checksum_start = entry.find('[').ok_or_else(|| ParseError::at_end(entry, "a checksum in brackets"))?;
+    if !entry.ends_with(']') {
+        return Err(ParseError::at_end(entry, "a closing bracket"));
+    }
+
+    let sector_start = entry[..checksum_start].rfind('-').map_or(0, |i| i + 1);
+    let name = &entry[..sector_start];
+    if let Some((i, c)) = name.char_indices().find(|&(_, c)| !(c.is_ascii_lowercase() || c == '-')) {
+        return Err(ParseError::at(entry, &name[i..i + c.len_utf8()], "a lowercase letter or dash"));
+    }
+
+    Ok(Room(name.chars().filter(|&c| c != '-').collect(),
+            token(entry, &entry[sector_start..checksum_start], "a sector id")?,
+            entry[checksum_start + 1..entry.len() - 1].to_string(),
+            entry.to_string()))
+}
+
+fn get_real_rooms(encrypted_data: &str) -> Result<Vec<Room>, ParseError> {
+    Ok(parse_lines(encrypted_data, parse_room)?.into_iter().filter(|room| {
+        room.2 == generate_checksum(&room.0)
+    }).collect())
+}
+
+pub fn get_sector_id_total(input: &str) -> Result<u32, ParseError> {
+    Ok(get_real_rooms(input)?.iter().fold(0, | sector_id_total, room | {
+        sector_id_total + room.1
+    }))
+}
+
+fn shift_cipher(shift_char: &char, shift_by: &u32) -> char {
+    match *shift_char {
+        '-' => ' ',
+        x => {
+            let (low, high) = ('a' as u32, 'z' as u32);
+            let range = high - low + 1;  
+            let shift = (*shift_by % range) as u8;
+            let normalised_char = ((x as u8 - low as u8) + shift) % range as u8;
+            (normalised_char + low as u8) as char
+        }
+    }
+}
+
+fn get_real_room_name(encrypted_room: &str, sector_id: &u32) -> String {
+    let real_name : String = encrypted_room.split(char::is_numeric).collect::<Vec<&str>>()[0].chars().map(|c| shift_cipher(&c, sector_id)).collect();
+    real_name.trim().to_string()
+}
+
+pub fn get_north_pole_storage_sector_id(input: &str) -> Result<u32, ParseError> {
+    get_real_rooms(input)?.iter().filter_map(|room| {
+        match get_real_room_name(&room.3, &room.1) == "northpole object storage" {
+            true => Some(room.1),
+            false => None
+        }
+    }).next().ok_or_else(|| ParseError::at_end_of_input(input, "a real room named northpole object storage"))
+}
+
+fn encrypt_room(name: &str, sector_id: u32, rng: &mut Rng, real: bool) -> String {
+    let encrypted = name.chars().map(|c| match c {
+        ' ' => '-',
+        c => shift_cipher(&c, &(26 - sector_id % 26)),
+    }).collect::<String>();
+
+    let real_checksum = generate_checksum(&encrypted.replace('-', ""));
+    let checksum = match real {
+        true => real_checksum,
+        false => (0..).map(|_| rng.word(LOWERCASE, 5)).find(|decoy| *decoy != real_checksum).unwrap(),
+    };
+    format!("{}-{}[{}]", encrypted, sector_id, checksum)
+}
+
+// `size` rooms, about half of them real, plus the real north pole storage room. Returns the input,
+// the sum of the real sector ids and the storage room's sector id.
+fn generate_rooms(rng: &mut Rng, size: usize) -> (String, u32, u32) {
+    let storage_sector = rng.range(100, 999) as u32;
+    let mut rooms = vec!((encrypt_room("northpole object storage", storage_sector, rng, true), storage_sector));
+
+    for _ in 0..size {
+        let words = (0..rng.range(1, 5)).map(|_| {
+            let length = rng.range(1, 10) as usize;
+            rng.word(LOWERCASE, length)
+        }).collect::<Vec<String>>();
+        let sector_id = rng.range(100, 999) as u32;
+        let real = rng.one_in(2);
+        rooms.push((encrypt_room(&words.join(" "), sector_id, rng, real), if real { sector_id } else { 0 }));
+    }
+
+    rng.shuffle(&mut rooms);
+    let total = rooms.iter().map(|room| room.1).sum();
+    (rooms.into_iter().map(|room| room.0).collect::<Vec<String>>().join("\n"), total, storage_sector)
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    fn part_one(&self, input: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(get_sector_id_total(input)?.into())
+    }
+
+    fn part_two(&self, input: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(get_north_pole_storage_sector_id(input)?.into())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate_rooms(rng, size).0
+    }
+}
+
+#[test]
+fn part_one() {
+    let inputs = "aaaaa-bbb-z-y-x-123[abxyz]
+a-b-c-d-e-f-g-h-987[abcde]
+not-a-real-room-404[oarel]
+totally-real-room-200[decoy]";
+    assert_eq!(Ok(1514), get_sector_id_total(inputs));
+}
+
+#[test]
+fn part_two() {
+    let inputs = "qzmt-zixmtkozy-ivhz";
+    assert_eq!("very encrypted name", &get_real_room_name(inputs, &343));
+}
+
+#[test]
+fn bad_rooms() {
+    assert_eq!(Err(ParseError::new(2, 13, "", "a checksum in brackets")), get_sector_id_total("a-b-c-d-e-f-g-h-987[abcde]\nnot-a-room-1"));
+    assert_eq!(Err(ParseError::new(1, 16, "", "a closing bracket")), get_sector_id_total("not-a-room-1[ab"));
+    assert_eq!(Err(ParseError::new(1, 12, "40x", "a sector id")), get_sector_id_total("not-a-room-40x[oarel]"));
+    assert_eq!(Err(ParseError::new(1, 5, "A", "a lowercase letter or dash")), get_sector_id_total("not-A-room-404[oarel]"));
+}
+
+#[test]
+fn no_storage_room() {
+    assert_eq!(Ok(0), get_sector_id_total(""));
+    assert_eq!(Err(ParseError::new(1, 1, "", "a real room named northpole object storage")), get_north_pole_storage_sector_id(""));
+    assert_eq!(Err(ParseError::new(1, 27, "", "a real room named northpole object storage")), get_north_pole_storage_sector_id("aaaaa-bbb-z-y-x-123[abxyz]"));
+}
+
+#[test]
+fn generated_rooms() {
+    for seed in 0..20 {
+        let (input, total, storage_sector) = generate_rooms(&mut Rng::new(seed), 40);
+        assert_eq!(Ok(total), get_sector_id_total(&input));
+        assert_eq!(Ok(storage_sector), get_north_pole_storage_sector_id(&input));
+    }
+}
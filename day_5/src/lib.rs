@@ -0,0 +1,129 @@
+extern crate aoc_core;
+extern crate md5;
+
+use std::char;
+use aoc_core::parse::ParseError;
+use aoc_core::params::Params;
+use aoc_core::random::{LOWERCASE, Rng};
+use aoc_core::solution::{Answer, Solution};
+use aoc_core::trace;
+
+fn hex_to_char(hex: u8) -> char {
+	char::from_digit(hex as u32, 16).unwrap()
+}
+
+fn is_valid_hash(digest: &[u8]) -> bool {
+	digest[0] as u32 + digest[1] as u32 + (digest[2] as u32 >> 4) == 0
+}
+
+fn get_char_index_from_valid_hash_byte(hash_byte: u8, max_index: usize) -> Option<usize> {
+	let index = (hash_byte) as usize;
+	match index < max_index {
+		true => Some(index),
+		false => None
+	}
+}
+
+fn find_next_unused_hash(door_id: &str, start_index: u32, invalid_indices: &[char]) -> (char, usize, u32) {
+	for index in start_index.. {
+		let next_door_id = door_id.to_string() + &index.to_string();
+		let next_digest = md5::compute(next_door_id.to_string().into_bytes());
+
+		if is_valid_hash(&next_digest.0) {
+			let placement = get_char_index_from_valid_hash_byte(&next_digest[2] & 0x0f, invalid_indices.len());
+			if let Some(placement_index) = placement {
+				if invalid_indices[placement_index] == ' ' {
+					return (hex_to_char(next_digest[3] >> 4), placement_index, index);
+				}
+			}
+		}
+	}
+
+	panic!("Should not get here!");
+}
+
+fn find_next_hash(door_id: &str, start_index: u32) -> (char, u32) {
+	for index in start_index.. {
+		let next_door_id = door_id.to_string() + &index.to_string();
+		let next_digest = md5::compute(next_door_id.to_string().into_bytes());
+		
+		if is_valid_hash(&next_digest.0) {
+			return (hex_to_char(next_digest[2] & 0x0f), index);
+		}
+	}
+
+	panic!("Should not get here!");
+}
+
+pub fn parse_door_id(input: &str) -> Result<&str, ParseError> {
+	let door_id = input.trim();
+	match door_id.split_whitespace().nth(1) {
+		Some(extra) => Err(ParseError::at(door_id, extra, "a single door id")),
+		None if door_id.is_empty() => Err(ParseError::at_end(door_id, "a door id")),
+		None => Ok(door_id)
+	}
+}
+
+pub fn get_door_password_part_one(door_id: &str, len: usize) -> String {
+	(0..len).scan(0, |start_index, _| {
+		let (next_char, found_at) = find_next_hash(door_id, *start_index);
+		*start_index = found_at + 1;
+		Some(next_char)
+	}).collect()
+}
+
+pub fn get_door_password_part_two(door_id: &str, len: usize) -> String {
+	(0..len).fold((0, vec!(' ',' ',' ',' ',' ',' ',' ',' ')), | state, _ | {
+		trace!("Password so far {:?}, searching from index {}", state.1.iter().collect::<String>(), state.0);
+		let (next_char, placement_index, found_at) = find_next_unused_hash(door_id, state.0, &state.1);
+		let mod_vec = (0..len).map(|i| if i == placement_index { next_char } else {' '}).collect::<Vec<char>>();
+		(found_at + 1, state.1.iter().zip(mod_vec.iter()).map(|(l,r)| if *r != ' ' { *r } else { *l } ).collect())
+	}).1.into_iter().collect()
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+	fn part_one(&self, input: &str, params: &Params) -> Result<Answer, ParseError> {
+		Ok(get_door_password_part_one(parse_door_id(input)?, params.get("password_length", 8)?).into())
+	}
+
+	fn part_two(&self, input: &str, params: &Params) -> Result<Answer, ParseError> {
+		Ok(get_door_password_part_two(parse_door_id(input)?, params.get("password_length", 8)?).into())
+	}
+
+	// A door id `size` letters long; solving it still takes millions of hashes whatever the size
+	fn generate(&self, rng: &mut Rng, size: usize) -> String {
+		rng.word(LOWERCASE, std::cmp::max(1, size))
+	}
+}
+
+#[test]
+fn part_one() {
+	assert_eq!(('1', 3231929), find_next_hash("abc", 0));
+	assert_eq!(('8', 5017308), find_next_hash("abc", 3231929+1));
+	assert_eq!(('f', 5278568), find_next_hash("abc", 5017308+1));
+	assert_eq!("18f47a30", get_door_password_part_one("abc", 8));
+}
+
+#[test]
+fn part_two() {
+	assert_eq!(('5', 1, 3231929), find_next_unused_hash("abc", 3231929, &[' '; 8]));
+	assert_eq!(('e', 4, 5357525), find_next_unused_hash("abc", 5357525, &[' '; 8]));
+	assert_eq!("05ace8e3", get_door_password_part_two("abc", 8));
+}
+
+#[test]
+fn generated_door_ids() {
+	for seed in 0..20 {
+		let door_id = Day5.generate(&mut Rng::new(seed), 8);
+		assert_eq!(Ok(door_id.as_str()), parse_door_id(&door_id));
+	}
+}
+
+#[test]
+fn door_ids() {
+	assert_eq!(Ok("abc"), parse_door_id("abc\n"));
+	assert_eq!(Err(ParseError::new(1, 1, "", "a door id")), parse_door_id("  \n"));
+	assert_eq!(Err(ParseError::new(1, 5, "def", "a single door id")), parse_door_id("abc def"));
+}
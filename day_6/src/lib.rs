@@ -0,0 +1,189 @@
+extern crate aoc_core;
+
+use aoc_core::math::{char_to_index, index_to_char};
+use aoc_core::parse::ParseError;
+use aoc_core::params::Params;
+use aoc_core::random::{LOWERCASE, Rng};
+use aoc_core::solution::{Answer, Solution};
+
+fn get_char_count_array(char_array: &[char]) -> Vec<i32> {
+    char_array.iter().fold(vec![0; 1 + ('z' as u32 - 'a' as u32) as usize], |count_array, c| {
+            count_array.iter().enumerate().map(|(i,x)| if i == char_to_index(*c) {*x+1} else {*x}).collect::<Vec<i32>>()
+    })
+}
+
+fn get_least_frequent_char(char_counts: &[i32]) -> char {
+    index_to_char(char_counts.iter().enumerate().fold((char_counts.len() as i32,0), |current_min, (i,count)| {
+            if *count > 0 && *count < current_min.0 {
+                (*count,i)
+            }
+            else {
+                current_min
+            }
+    }).1)
+}
+
+fn get_most_frequent_char(char_counts: &[i32]) -> char {
+    index_to_char(char_counts.iter().enumerate().fold((0,0), |current_max, (i,count)| {
+            if count > &current_max.0 {
+                (*count,i)
+            }
+            else {
+                current_max
+            }
+    }).1)
+}
+
+fn parse_message(message: &str) -> Result<Vec<&str>, ParseError> {
+    let lines = message.lines().collect::<Vec<&str>>();
+    let message_len = lines.first().map_or(0, |line| line.len());
+
+    for (line_index, line) in lines.iter().enumerate() {
+        if let Some((i, c)) = line.char_indices().find(|&(_, c)| !c.is_ascii_lowercase()) {
+            return Err(ParseError::at(line, &line[i..i + c.len_utf8()], "a lowercase letter").on_line(line_index + 1));
+        }
+
+        if line.is_empty() || line.len() != message_len {
+            let expected = format!("a message {} letters long", message_len);
+            return Err(ParseError::at(line, line, &expected).on_line(line_index + 1));
+        }
+    }
+
+    match lines.is_empty() {
+        true => Err(ParseError::new(1, 1, "", "a message")),
+        false => Ok(lines)
+    }
+}
+
+fn get_char_columns(message: &str) -> Result<Vec<Vec<char>>, ParseError> {
+    let lines = parse_message(message)?;
+    let num_rows = lines[0].len();
+    let chars : Vec<char> = lines.iter().flat_map(|s| s.chars()).collect();
+
+    Ok((0..num_rows).fold(vec!(), |columns, row_index| {
+        let next_column = [chars.iter().enumerate().filter_map(|(i,c)| if i % num_rows == row_index {Some(*c)} else {None}).collect::<Vec<char>>()];
+        columns.iter().chain(next_column.iter()).cloned().collect()
+    }))
+}
+
+pub fn correct_message_part_one(message: &str) -> Result<String, ParseError> {
+    Ok(get_char_columns(message)?.iter().map(|x| {
+        get_most_frequent_char(&get_char_count_array(x))
+    }).collect())
+}
+
+pub fn correct_message_part_two(message: &str) -> Result<String, ParseError> {
+    Ok(get_char_columns(message)?.iter().map(|x| {
+        get_least_frequent_char(&get_char_count_array(x))
+    }).collect())
+}
+
+// One column of noise where `most` is the clear favourite and `least` the only letter seen once
+fn generate_column(rng: &mut Rng, rows: usize, most: char, least: char) -> Vec<char> {
+    let others = LOWERCASE.chars().filter(|&c| c != most && c != least).collect::<Vec<char>>();
+    let mut column = vec![most; rows / 2 + 1];
+    column.push(least);
+
+    let mut remaining = rows - column.len();
+    if remaining == 1 {
+        column.push(most);
+        remaining = 0;
+    }
+    while remaining > 0 {
+        let count = if remaining == 3 { 3 } else { 2 };
+        column.extend(vec![*rng.pick(&others); count]);
+        remaining -= count;
+    }
+
+    rng.shuffle(&mut column);
+    column
+}
+
+// `size` rows (at least four) of an eight letter message. Returns the input along with the
+// messages hidden in its most and least common letters.
+fn generate_message(rng: &mut Rng, size: usize) -> (String, String, String) {
+    let rows = std::cmp::max(4, size);
+    let letters = LOWERCASE.chars().collect::<Vec<char>>();
+    let most = rng.word(LOWERCASE, 8);
+    let least = most.chars().map(|m| (0..).map(|_| *rng.pick(&letters)).find(|&c| c != m).unwrap()).collect::<String>();
+
+    let columns = most.chars().zip(least.chars()).map(|(m, l)| generate_column(rng, rows, m, l)).collect::<Vec<Vec<char>>>();
+    let input = (0..rows).map(|row| columns.iter().map(|column| column[row]).collect::<String>()).collect::<Vec<String>>().join("\n");
+    (input, most, least)
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    fn part_one(&self, input: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(correct_message_part_one(input)?.into())
+    }
+
+    fn part_two(&self, input: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(correct_message_part_two(input)?.into())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate_message(rng, size).0
+    }
+}
+
+#[test]
+fn part_one() {
+    let input = "eedadn
+drvtee
+eandsr
+raavrd
+atevrs
+tsrnev
+sdttsa
+rasrtv
+nssdts
+ntnada
+svetve
+tesnvt
+vntsnd
+vrdear
+dvrsen
+enarar";
+
+    assert_eq!(Ok("easter".to_string()), correct_message_part_one(input));
+}
+
+#[test]
+fn part_two() {
+    let input = "eedadn
+drvtee
+eandsr
+raavrd
+atevrs
+tsrnev
+sdttsa
+rasrtv
+nssdts
+ntnada
+svetve
+tesnvt
+vntsnd
+vrdear
+dvrsen
+enarar";
+
+    assert_eq!(Ok("advent".to_string()), correct_message_part_two(input));
+}
+
+#[test]
+fn bad_messages() {
+    assert_eq!(Err(ParseError::new(1, 1, "", "a message")), correct_message_part_one(""));
+    assert_eq!(Err(ParseError::new(2, 3, "D", "a lowercase letter")), correct_message_part_one("eedadn\ndrDtee"));
+    assert_eq!(Err(ParseError::new(2, 1, "drvte", "a message 6 letters long")), correct_message_part_one("eedadn\ndrvte"));
+}
+
+#[test]
+fn generated_messages() {
+    for seed in 0..20 {
+        let (input, most, least) = generate_message(&mut Rng::new(seed), seed as usize + 4);
+        assert_eq!(Ok(most), correct_message_part_one(&input));
+        assert_eq!(Ok(least), correct_message_part_two(&input));
+    }
+}
@@ -0,0 +1,143 @@
+extern crate aoc_core;
+
+use aoc_core::parse::ParseError;
+use aoc_core::params::Params;
+use aoc_core::random::{LOWERCASE, Rng};
+use aoc_core::solution::{Answer, Solution};
+
+fn check_segment_for_abba(segment: &str) -> bool {
+    for window in segment.chars().collect::<Vec<char>>().windows(4) {
+        if window[0] != window[1] && window[0] == window[3] && window[1] == window[2] {
+            return true;
+        }
+    }
+
+    false
+}
+
+fn check_segment_for_aba(segment: &str) -> Vec<String> {
+    segment.chars().collect::<Vec<char>>().windows(3).fold(vec!(), | aba_vec, window | {
+        if window[0] != window[1] && window[0] == window[2] {
+            return aba_vec.iter().chain([window.iter().copied().collect()].iter()).cloned().collect();
+        }
+
+        aba_vec
+    })
+}
+
+fn reverse_aba(aba: &str) -> String {
+    vec!(aba.chars().nth(1).unwrap(), aba.chars().next().unwrap(), aba.chars().nth(1).unwrap()).into_iter().collect()
+}
+
+fn does_address_support_ssl(address_line: &str) -> bool {
+    let (supernet_aba, hypernet_aba) = address_line.split(['[', ']']).enumerate().fold((vec!(), vec!()), | (supernet, hypernet), (segment_index, segment) | {
+        let abba_segment = check_segment_for_aba(segment);
+
+        if !abba_segment.is_empty() {
+            if segment_index % 2 == 0 {
+                return (supernet.iter().chain(abba_segment.iter()).cloned().collect(), hypernet);
+            }
+            else {
+                return (supernet, hypernet.iter().chain(abba_segment.iter()).cloned().collect());
+            }
+        }
+
+        (supernet, hypernet)
+    });
+
+    for aba in supernet_aba {
+        if hypernet_aba.contains(&reverse_aba(&aba)) {
+            return true;
+        }
+    }
+
+    false
+}
+
+fn does_address_support_tls(address_line: &str) -> bool {
+    let counts = address_line.split(['[', ']']).enumerate().fold((0, 0), | (matched_segments, matched_hypernets), (segment_index, segment) | {
+        let abba_segment = check_segment_for_abba(segment);
+
+        if segment_index % 2 != 0 && abba_segment {
+            return (matched_segments, matched_hypernets + 1);
+        }
+
+        (matched_segments + (abba_segment as i32), matched_hypernets)
+    });
+
+    counts.0 > 0 && counts.1 == 0
+}
+
+pub fn get_tls_address_count(address_lines: &str) -> i32 {
+    address_lines.lines().fold(0, |acc, line| {
+        acc + (does_address_support_tls(line) as i32)
+    })
+}
+
+pub fn get_ssl_address_count(address_lines: &str) -> i32 {
+    address_lines.lines().fold(0, |acc, line| {
+        acc + (does_address_support_ssl(line) as i32)
+    })
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    fn part_one(&self, input: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(get_tls_address_count(input).into())
+    }
+
+    fn part_two(&self, input: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(get_ssl_address_count(input).into())
+    }
+
+    // `size` addresses. Half of them only use a few letters so ABBAs and ABAs turn up often, and
+    // any segment but the first may be empty.
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        (0..size).map(|_| {
+            let alphabet = if rng.one_in(2) { "abc" } else { LOWERCASE };
+            let hypernets = rng.range(0, 3) as usize;
+            (0..2 * hypernets + 1).map(|i| {
+                let length = rng.range(if i == 0 { 1 } else { 0 }, 12) as usize;
+                let segment = rng.word(alphabet, length);
+                match i % 2 {
+                    0 => segment,
+                    _ => format!("[{}]", segment),
+                }
+            }).collect::<String>()
+        }).collect::<Vec<String>>().join("\n")
+    }
+}
+
+#[test]
+fn part_one() {
+    assert!(does_address_support_tls("abba[mnop]qrst"));
+    assert!(!does_address_support_tls("abcd[bddb]xyyx"));
+    assert!(!does_address_support_tls("aaaa[qwer]tyui"));
+    assert!(does_address_support_tls("ioxxoj[asdfgh]zxcvbn"));
+}
+
+#[test]
+fn part_two() {
+    assert!(does_address_support_ssl("aba[bab]xyz"));
+    assert!(!does_address_support_ssl("xyx[xyx]xyx"));
+    assert!(does_address_support_ssl("aaa[kek]eke"));
+    assert!(does_address_support_ssl("zazbz[bzb]cdb"));
+} 
+#[test]
+fn reversed_segments() {
+    // ABBAs and ABAs read the same backwards, so reversing every segment in place changes nothing
+    for seed in 0..20 {
+        let input = Day7.generate(&mut Rng::new(seed), 50);
+        let reversed = input.lines().map(|line| {
+            line.split_inclusive(['[', ']']).map(|segment| {
+                let (text, bracket) = segment.split_at(segment.trim_end_matches(['[', ']']).len());
+                text.chars().rev().collect::<String>() + bracket
+            }).collect::<String>()
+        }).collect::<Vec<String>>().join("\n");
+
+        assert!(reversed != input);
+        assert_eq!(get_tls_address_count(&input), get_tls_address_count(&reversed));
+        assert_eq!(get_ssl_address_count(&input), get_ssl_address_count(&reversed));
+    }
+}
@@ -0,0 +1,284 @@
+extern crate aoc_core;
+
+use std::fmt;
+use std::io::Write;
+use aoc_core::grid::Grid;
+use aoc_core::parse::{ParseError, field, parse_lines, token};
+use aoc_core::pattern::{FromCapture, Pattern};
+use aoc_core::params::Params;
+use aoc_core::random::Rng;
+use aoc_core::render::Renderer;
+use aoc_core::solution::{Answer, Solution};
+use aoc_core::svg::Picture;
+use aoc_core::trace;
+
+#[derive(Debug,PartialEq)]
+enum Instruction {
+    Rect{x: i32, y: i32},
+    RotateRow{row_index: i32, rotate_by: i32},
+    RotateCol{col_index: i32, rotate_by: i32}
+}
+
+struct Screen {
+    grid: Grid<bool>
+}
+
+impl Screen {
+    fn get_lit_pixels(&self) -> i32 {
+        self.grid.cells().filter(|pixel| **pixel).count() as i32
+    }
+
+    fn render_screen(&self) -> String {
+        self.grid.render(|pixel| if *pixel { '#' } else { '.' })
+    }
+}
+
+impl fmt::Debug for Screen {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.render_screen())
+    }
+}
+
+// A rectangle's size, like 3x2
+struct Dimensions(i32, i32);
+
+impl<'a> FromCapture<'a> for Dimensions {
+    fn from_capture(line: &'a str, text: &'a str, expected: &str) -> Result<Dimensions, ParseError> {
+        match text.split_once('x') {
+            Some((x, y)) => Ok(Dimensions(token(line, x, "a width")?, token(line, y, "a height")?)),
+            None => Err(ParseError::at(line, text, expected)),
+        }
+    }
+}
+
+fn parse_instruction(instruction: &str) -> Result<Instruction, ParseError> {
+    let split_instr = instruction.split_whitespace().collect::<Vec<&str>>();
+    match field(instruction, &split_instr, 0, "an instruction")? {
+        "rect" => {
+            let Dimensions(x, y) = Pattern::new("rect {rectangle dimensions like 3x2}").parse(instruction)?;
+            Ok(Instruction::Rect{x, y})
+        },
+        "rotate" => match field(instruction, &split_instr, 1, "row or column")? {
+            "row" => {
+                let (row_index, rotate_by) = Pattern::new("rotate row y={a row index} by {a rotation distance}").parse(instruction)?;
+                Ok(Instruction::RotateRow{row_index, rotate_by})
+            },
+            "column" => {
+                let (col_index, rotate_by) = Pattern::new("rotate column x={a column index} by {a rotation distance}").parse(instruction)?;
+                Ok(Instruction::RotateCol{col_index, rotate_by})
+            },
+            x => Err(ParseError::at(instruction, x, "row or column"))
+        },
+        x => Err(ParseError::at(instruction, x, "rect or rotate"))
+    }
+}
+
+fn construct_screen(screen_dimensions: (usize, usize)) -> Screen {
+    Screen { grid: Grid::new(screen_dimensions.0, screen_dimensions.1, false) }
+}
+
+// Rows and columns off the edge of the screen are left alone
+fn apply_instruction(next_instruction: Instruction, last_state: &Screen) -> Screen {
+    let mut grid = last_state.grid.clone();
+    match next_instruction {
+        Instruction::Rect{x, y} => {
+            for point in last_state.grid.points().filter(|&(col, row)| col < x && row < y) {
+                grid[point] = true;
+            }
+        },
+        Instruction::RotateRow{row_index, rotate_by} => {
+            if (row_index as usize) < grid.height() {
+                grid.rotate_row(row_index as usize, rotate_by);
+            }
+        },
+        Instruction::RotateCol{col_index, rotate_by} => {
+            if (col_index as usize) < grid.width() {
+                grid.rotate_column(col_index as usize, rotate_by);
+            }
+        }
+    }
+
+    Screen { grid }
+}
+
+fn run_screen(instructions: &str, screen_dimensions: (usize, usize)) -> Result<Screen, ParseError> {
+    run_screen_with(instructions, screen_dimensions, |screen| trace!("\n{:?}", screen))
+}
+
+fn run_screen_with<F: FnMut(&Screen)>(instructions: &str, screen_dimensions: (usize, usize), mut on_frame: F) -> Result<Screen, ParseError> {
+    let initial_screen = construct_screen(screen_dimensions);
+    Ok(parse_lines(instructions, parse_instruction)?.into_iter().fold(initial_screen, | last_state, instr | {
+        let new_grid = apply_instruction(instr, &last_state);
+
+        on_frame(&new_grid);
+        new_grid
+    }))
+}
+
+// Redraws the screen after every instruction, and gives back the final picture
+pub fn animate_screen<W: Write>(instructions: &str, screen_dimensions: (usize, usize), renderer: &mut Renderer<W>) -> Result<String, ParseError> {
+    let screen = run_screen_with(instructions, screen_dimensions, |screen| renderer.frame(|| screen.render_screen()))?;
+    renderer.finish(|| screen.render_screen());
+    Ok(screen.render_screen())
+}
+
+pub fn count_lit_pixels(instructions: &str, screen_dimensions: (usize, usize)) -> Result<i32, ParseError> {
+    Ok(run_screen(instructions, screen_dimensions)?.get_lit_pixels())
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    fn part_one(&self, input: &str, params: &Params) -> Result<Answer, ParseError> {
+        Ok(count_lit_pixels(input, params.get_pair("screen_size", (50,6))?)?.into())
+    }
+
+    fn part_two(&self, input: &str, params: &Params) -> Result<Answer, ParseError> {
+        Ok(Answer::Art(run_screen(input, params.get_pair("screen_size", (50,6))?)?.render_screen()))
+    }
+
+    // `size` instructions for the default 50x6 screen, including rotations by nothing and by a whole turn
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate_instructions(rng, size, (50, 6))
+    }
+
+    fn picture(&self, input: &str, params: &Params) -> Result<Option<Picture>, ParseError> {
+        Ok(Some(Picture::grid(run_screen(input, params.get_pair("screen_size", (50,6))?)?.grid)))
+    }
+}
+
+fn generate_instructions(rng: &mut Rng, size: usize, (width, height): (usize, usize)) -> String {
+    let (width, height) = (width as i64, height as i64);
+    (0..size).map(|_| match rng.below(3) {
+        0 => format!("rect {}x{}", rng.range(1, width), rng.range(1, height)),
+        1 => format!("rotate row y={} by {}", rng.range(0, height - 1), rng.range(0, width)),
+        _ => format!("rotate column x={} by {}", rng.range(0, width - 1), rng.range(0, height)),
+    }).collect::<Vec<String>>().join("\n")
+}
+
+#[test]
+fn instruction_parsing() {
+    assert_eq!(Ok(Instruction::Rect{x: 3, y: 2}), parse_instruction("rect 3x2"));
+    assert_eq!(Ok(Instruction::RotateCol{col_index: 1, rotate_by: 1}), parse_instruction("rotate column x=1 by 1"));
+    assert_eq!(Ok(Instruction::RotateRow{row_index: 0, rotate_by: 4}), parse_instruction("rotate row y=0 by 4"));
+}
+
+#[test]
+fn bad_instructions() {
+    assert_eq!(Err(ParseError::new(1, 1, "", "an instruction")), parse_instruction(""));
+    assert_eq!(Err(ParseError::new(1, 1, "flip", "rect or rotate")), parse_instruction("flip 3x2"));
+    assert_eq!(Err(ParseError::new(1, 6, "3by2", "rectangle dimensions like 3x2")), parse_instruction("rect 3by2"));
+    assert_eq!(Err(ParseError::new(1, 8, "y", "a height")), parse_instruction("rect 3xy"));
+    assert_eq!(Err(ParseError::new(1, 8, "diagonal", "row or column")), parse_instruction("rotate diagonal x=1 by 1"));
+    assert_eq!(Err(ParseError::new(1, 21, "", "a rotation distance")), parse_instruction("rotate column x=1 by"));
+    assert_eq!(Err(ParseError::new(2, 16, "to", "\"by\"")), count_lit_pixels("rect 1x1\nrotate row y=0 to 4", (7,3)));
+    assert_eq!(Err(ParseError::new(1, 12, "x=0", "a row index")), parse_instruction("rotate row x=0 by 4"));
+}
+
+#[test]
+fn instruction_sequence() {
+    let input = "rect 3x2
+    rotate column x=1 by 1
+    rotate row y=0 by 4
+    rotate column x=1 by 1";
+    assert_eq!(Ok(6), count_lit_pixels(input, (7,3)));
+}
+
+#[test]
+fn test_instructions() {
+    let mut input = "rect 3x2";
+    let mut output = "###....
+###....
+.......";
+
+    let mut last_state = apply_instruction(parse_instruction(input).unwrap(), &construct_screen((7,3)));
+    assert_eq!(output, last_state.render_screen());
+
+    input = "rotate column x=1 by 1";
+    output = "#.#....
+###....
+.#.....";
+
+    last_state = apply_instruction(parse_instruction(input).unwrap(), &last_state);
+    assert_eq!(output, last_state.render_screen());
+
+    input = "rotate row y=0 by 4";
+    output = "....#.#
+###....
+.#.....";
+
+    last_state = apply_instruction(parse_instruction(input).unwrap(), &last_state);
+    assert_eq!(output, last_state.render_screen());
+
+    input = "rotate column x=1 by 1";
+    output = ".#..#.#
+#.#....
+.#.....";
+
+    last_state = apply_instruction(parse_instruction(input).unwrap(), &last_state);
+    assert_eq!(output, last_state.render_screen());
+}
+
+#[test]
+fn output_test() {
+    let screen_test = ".......
+.......
+.......";
+
+    assert_eq!(screen_test, construct_screen((7,3)).render_screen());
+}
+
+#[test]
+fn rendered_answer() {
+    let input = "rect 3x2
+rotate column x=1 by 1
+rotate row y=0 by 4
+rotate column x=1 by 1";
+
+    assert_eq!(Answer::Art(".#..#.#\n#.#....\n.#.....".to_string()), Answer::Art(run_screen(input, (7,3)).unwrap().render_screen()));
+}
+
+#[test]
+fn animated_screen() {
+    let input = "rect 3x2\nrotate column x=1 by 1\nrotate row y=0 by 4\nrotate column x=1 by 1";
+    let mut renderer = Renderer::new(Vec::new(), true, 0);
+    assert_eq!(".#..#.#\n#.#....\n.#.....", animate_screen(input, (7,3), &mut renderer).unwrap());
+
+    let drawn = String::from_utf8(renderer.into_inner()).unwrap();
+    assert_eq!(4, drawn.matches("\x1b[J").count());
+    assert!(drawn.ends_with(".#..#.#\n#.#....\n.#.....\n\x1b[?25h"));
+}
+
+#[test]
+fn configured_screen() {
+    let input = "rect 3x2
+rotate column x=1 by 1
+rotate row y=0 by 4
+rotate column x=1 by 1";
+
+    let params = Params::new().with("screen_size", "7,3");
+    assert_eq!(Ok(Answer::Int(6)), Day8.part_one(input, &params));
+    assert_eq!(Ok(Answer::Art(".#..#.#\n#.#....\n.#.....".to_string())), Day8.part_two(input, &params));
+}
+
+#[test]
+fn full_turns() {
+    // Rotating a row by the screen's width, or a column by its height, leaves the screen as it was
+    for seed in 0..10 {
+        let mut rng = Rng::new(seed);
+        let input = generate_instructions(&mut rng, 20, (7, 3));
+        let padded = input.lines().flat_map(|line| vec!(line.to_string(), format!("rotate row y={} by 7", rng.range(0, 2)), format!("rotate column x={} by 3", rng.range(0, 6))))
+            .collect::<Vec<String>>().join("\n");
+
+        assert_eq!(run_screen(&input, (7, 3)).unwrap().render_screen(), run_screen(&padded, (7, 3)).unwrap().render_screen());
+    }
+}
+
+#[test]
+fn pictured_screen() {
+    let params = Params::new().with("screen_size", "7,3");
+    let picture = Day8.picture("rect 3x2\nrotate column x=1 by 1", &params).unwrap().unwrap();
+    let grid = picture.grid.unwrap();
+    assert_eq!((7, 3), (grid.width(), grid.height()));
+    assert_eq!("#.#....\n###....\n.#.....", grid.render(|pixel| if *pixel { '#' } else { '.' }));
+}
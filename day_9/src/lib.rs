@@ -0,0 +1,174 @@
+extern crate aoc_core;
+
+use aoc_core::parse::{ParseError, token};
+use aoc_core::params::Params;
+use aoc_core::random::Rng;
+use aoc_core::solution::{Answer, Solution};
+
+fn decompress_segment(input: &str, segment: &str, decompress_repeated_segments: bool) -> Result<i64, ParseError> {
+    let next_marker_start = segment.find('(');
+    let next_marker_end = match next_marker_start {
+        Some(x) => segment[x..segment.len()].find(')'),
+        None => None
+    };
+
+    if next_marker_start.is_none() || next_marker_end.is_none() {
+        return Ok(segment.len() as i64);
+    }
+
+    let marker_start = next_marker_start.unwrap();
+    let marker_end = marker_start + next_marker_end.unwrap();
+    let marker = &segment[marker_start+1..marker_end];
+    let decode_marker = marker.split('x').collect::<Vec<&str>>();
+    if decode_marker.len() != 2 {
+        return Err(ParseError::at(input, marker, "a marker like 3x2"));
+    }
+
+    let sequence_length: usize = token(input, decode_marker[0], "a sequence length")?;
+    let repeat_count: i64 = token(input, decode_marker[1], "a repeat count")?;
+    let sequence_start = marker_end + 1;
+    let sequence_end = sequence_start + sequence_length;
+    if sequence_end > segment.len() {
+        return Err(ParseError::at(input, &segment[sequence_start..], &format!("{} characters to repeat", sequence_length)));
+    }
+
+    let rest = decompress_segment(input, &segment[sequence_end..segment.len()], decompress_repeated_segments)?;
+    if decompress_repeated_segments {
+        Ok(marker_start as i64 + (decompress_segment(input, &segment[sequence_start..sequence_end], true)? * repeat_count) + rest)
+    }
+    else {
+        Ok(marker_start as i64 + (sequence_length as i64 * repeat_count) + rest)
+    }
+}
+
+pub fn decompress(input: &str, decompress_repeated_segments: bool) -> Result<i64, ParseError> {
+    decompress_segment(input, input, decompress_repeated_segments)
+}
+
+// `pieces` runs of letters or markers, with markers only ever covering whole pieces so nested
+// markers stay intact. Returns the text with its decompressed lengths for both versions of the format.
+fn generate_compressed(rng: &mut Rng, pieces: usize, depth: usize) -> (String, i64, i64) {
+    (0..pieces).fold((String::new(), 0, 0), |(text, v1, v2), _| {
+        if depth >= 3 || rng.one_in(2) {
+            let length = rng.range(1, 6) as usize;
+            let letters = rng.word("ABCDEFGHIJKLMNOPQRSTUVWXYZ", length);
+            return (text + &letters, v1 + length as i64, v2 + length as i64);
+        }
+
+        let inner_pieces = rng.range(0, 3) as usize;
+        let (inner, _, inner_v2) = generate_compressed(rng, inner_pieces, depth + 1);
+        let repeat = if rng.one_in(10) { 0 } else { rng.range(1, 9) };
+        let marker = format!("({}x{})", inner.len(), repeat);
+        (text + &marker + &inner, v1 + inner.len() as i64 * repeat, v2 + inner_v2 * repeat)
+    })
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    fn part_one(&self, input: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(decompress(input, false)?.into())
+    }
+
+    fn part_two(&self, input: &str, _params: &Params) -> Result<Answer, ParseError> {
+        Ok(decompress(input, true)?.into())
+    }
+
+    // `size` top-level pieces, nested up to three markers deep, including empty and zero-repeat markers
+    fn generate(&self, rng: &mut Rng, size: usize) -> String {
+        generate_compressed(rng, size, 0).0
+    }
+}
+
+#[test]
+fn decompression_part_one() {
+    let mut input = "ADVENT";
+    assert_eq!(Ok(6), decompress(input, false));
+
+    input = "A(1x5)BC";
+    assert_eq!(Ok(7), decompress(input, false));
+
+    input = "(3x3)XYZ";
+    assert_eq!(Ok(9), decompress(input, false));
+    
+    input = "A(2x2)BCD(2x2)EFG";
+    assert_eq!(Ok(11), decompress(input, false));
+    
+    input = "(6x1)(1x3)A";
+    assert_eq!(Ok(6), decompress(input, false));
+    
+    input = "X(8x2)(3x3)ABCY";
+    assert_eq!(Ok(18), decompress(input, false));
+}
+
+#[test]
+fn decompression_part_2() {
+    let mut input = "(3x3)XYZ";
+    assert_eq!(Ok(9), decompress(input, true));
+
+    input = "X(8x2)(3x3)ABCY";
+    assert_eq!(Ok(20), decompress(input, true));
+
+    input = "(27x12)(20x12)(13x14)(7x10)(1x12)A";
+    assert_eq!(Ok(241920), decompress(input, true));
+    
+    input = "(25x3)(3x3)ABC(2x3)XY(5x2)PQRSTX(18x9)(3x2)TWO(5x7)SEVEN";
+    assert_eq!(Ok(445), decompress(input, true));
+}
+
+#[test]
+fn bad_markers() {
+    assert_eq!(Err(ParseError::new(1, 3, "1x2x3", "a marker like 3x2")), decompress("A(1x2x3)B", false));
+    assert_eq!(Err(ParseError::new(1, 5, "y", "a repeat count")), decompress("A(1xy)B", false));
+    assert_eq!(Err(ParseError::new(1, 7, "AB", "3 characters to repeat")), decompress("X(3x3)AB", true));
+    assert_eq!(Err(ParseError::new(1, 11, "BC", "3 characters to repeat")), decompress("(7x2)(3x3)BC", true));
+}
+
+#[test]
+fn generated_lengths() {
+    for seed in 0..50 {
+        let (input, v1, v2) = generate_compressed(&mut Rng::new(seed), 20, 0);
+        assert_eq!(Ok(v1), decompress(&input, false));
+        assert_eq!(Ok(v2), decompress(&input, true));
+    }
+}
+
+// Writes the decompressed text out in full, giving up wherever `decompress` reports an error
+#[cfg(test)]
+fn expand(text: &str, expand_repeated_segments: bool) -> Option<String> {
+    let mut expanded = String::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find('(') {
+        let end = match rest[start..].find(')') {
+            Some(offset) => start + offset,
+            None => break,
+        };
+        let (length, repeat) = rest[start + 1..end].split_once('x')?;
+        let (length, repeat) = (length.parse::<usize>().ok()?, repeat.parse::<usize>().ok()?);
+        let sequence = rest.get(end + 1..end + 1 + length)?;
+
+        expanded.push_str(&rest[..start]);
+        match expand_repeated_segments {
+            true => expanded.push_str(&expand(sequence, true)?.repeat(repeat)),
+            false => expanded.push_str(&sequence.repeat(repeat)),
+        }
+        rest = &rest[end + 1 + length..];
+    }
+
+    expanded.push_str(rest);
+    Some(expanded)
+}
+
+#[test]
+fn differential_expansion() {
+    use aoc_core::differential::{shrink_chars, Differential};
+
+    for expand_repeated_segments in [false, true] {
+        Differential::new(|rng| Day9.generate(rng, 6))
+            .shrink_with(|text| shrink_chars(text))
+            .implementation("counting", |text| decompress(text, expand_repeated_segments).ok())
+            .implementation("expanding", |text| expand(text, expand_repeated_segments).map(|e| e.len() as i64))
+            .check(200);
+    }
+}